@@ -0,0 +1,101 @@
+use aoc_core::{Error, ParseError};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// The known answers for one input file.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+}
+
+/// Every recorded answer, keyed by day (`day04`) and then by the input's file name without `.txt`
+/// (`data` for the real input, `test1`, `test2`, ... for the examples).
+#[derive(Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, Expected>>);
+
+impl Answers {
+    pub fn load(file: &str) -> Result<Self, Error> {
+        Ok(Self::parse(&aoc_core::read_input(file)?).map_err(|e| e.in_file(file))?)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        toml::from_str(input).map_err(|e| {
+            let offset = e.span().map_or(input.len(), |span| span.start);
+            let found = e.span().map_or("", |span| &input[span]);
+            // The error's message is multi-line with its own snippet, the first line is all we want
+            let message = e.message().lines().next().unwrap_or("").to_string();
+
+            match aoc_core::lines(input).find(|line| {
+                let start = line.text.as_ptr() as usize - input.as_ptr() as usize;
+                offset <= start + line.text.len()
+            }) {
+                Some(line) => {
+                    let start = line.text.as_ptr() as usize - input.as_ptr() as usize;
+                    let end = (start + line.text.len()).min(offset + found.len());
+                    line.error(&line.text[offset - start..end - start], &message)
+                }
+                None => ParseError::end_of_input(input, &message),
+            }
+        })
+    }
+
+    /// The inputs with answers recorded for `day`, always including the real `data` input.
+    pub fn inputs(&self, day: u32) -> Vec<String> {
+        let mut inputs = self
+            .0
+            .get(&day_key(day))
+            .map(|inputs| inputs.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        if !inputs.iter().any(|input| input == "data") {
+            inputs.push("data".to_string());
+        }
+        inputs
+    }
+
+    pub fn expected(&self, day: u32, input: &str, part: u8) -> Option<&str> {
+        self.0.get(&day_key(day))?.get(input)?.part(part)
+    }
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected() {
+        let answers = Answers::parse(
+            "[day04.test1]\npart1 = \"13\"\npart2 = \"43\"\n\n[day11.test2]\npart2 = \"2\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(answers.expected(4, "test1", 2), Some("43"));
+        assert_eq!(answers.expected(11, "test2", 1), None);
+        assert_eq!(answers.expected(5, "data", 1), None);
+        assert_eq!(answers.inputs(11), vec!["test2", "data"]);
+    }
+
+    #[test]
+    fn test_parse_error_points_at_value() {
+        let error = Answers::parse("[day04.test1]\npart1 = 13\n").unwrap_err();
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 9);
+        assert_eq!(error.found, "13");
+    }
+}
@@ -0,0 +1,136 @@
+use aoc_core::{Error, ParseError};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// The phases of a day that are timed separately.
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// A summary of the timings from repeatedly running one phase.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "at least one sample is needed");
+        samples.sort();
+        // Nearest rank, so every reported time is one we actually measured
+        let rank = |percentile: usize| samples[(samples.len() * percentile).div_ceil(100) - 1];
+        Self {
+            min_ns: samples[0].as_nanos() as u64,
+            median_ns: rank(50).as_nanos() as u64,
+            p95_ns: rank(95).as_nanos() as u64,
+        }
+    }
+
+    /// How much slower the median is than `baseline`'s, as a percentage. Negative is faster.
+    pub fn change_from(&self, baseline: &Stats) -> f64 {
+        (self.median_ns as f64 / baseline.median_ns.max(1) as f64 - 1.0) * 100.0
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?}  median {:>10.2?}  p95 {:>10.2?}",
+            Duration::from_nanos(self.min_ns),
+            Duration::from_nanos(self.median_ns),
+            Duration::from_nanos(self.p95_ns)
+        )
+    }
+}
+
+/// Runs `f` `iterations` times, returning the last result along with the timings.
+pub fn time<T>(iterations: usize, mut f: impl FnMut() -> T) -> (T, Stats) {
+    let mut samples = Vec::with_capacity(iterations);
+    let mut result = None;
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        result = Some(f());
+        samples.push(start.elapsed());
+    }
+    (result.unwrap(), Stats::from_samples(&mut samples))
+}
+
+/// Saved timings to compare later runs against, keyed by day (`day04`) and then phase.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub struct Baseline(BTreeMap<String, BTreeMap<String, Stats>>);
+
+impl Baseline {
+    /// Loads a saved baseline, or an empty one if nothing has been saved yet.
+    pub fn load(file: &str) -> Result<Self, Error> {
+        if !Path::new(file).exists() {
+            return Ok(Self::default());
+        }
+        let input = aoc_core::read_input(file)?;
+        Ok(serde_json::from_str(&input).map_err(|e| {
+            let message = e.to_string();
+            let message = message.split(" at line ").next().unwrap_or("");
+            match aoc_core::lines(&input).nth(e.line().saturating_sub(1)) {
+                Some(line) => ParseError::new(&line, e.column().max(1), "", message),
+                None => ParseError::end_of_input(&input, message),
+            }
+            .in_file(file)
+        })?)
+    }
+
+    pub fn save(&self, file: &str) -> Result<(), Error> {
+        let json = serde_json::to_string_pretty(self).expect("timings always serialize");
+        fs::write(file, json + "\n").map_err(|source| Error::Io {
+            file: file.to_string(),
+            source,
+        })
+    }
+
+    pub fn get(&self, day: u32, phase: &str) -> Option<&Stats> {
+        self.0.get(&format!("day{:02}", day))?.get(phase)
+    }
+
+    pub fn insert(&mut self, day: u32, phase: &str, stats: Stats) {
+        self.0
+            .entry(format!("day{:02}", day))
+            .or_default()
+            .insert(phase.to_string(), stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let mut samples = (1..=20)
+            .rev()
+            .map(Duration::from_millis)
+            .collect::<Vec<_>>();
+        let stats = Stats::from_samples(&mut samples);
+
+        assert_eq!(stats.min_ns, 1_000_000);
+        assert_eq!(stats.median_ns, 10_000_000);
+        assert_eq!(stats.p95_ns, 19_000_000);
+    }
+
+    #[test]
+    fn test_change_from() {
+        let stats = |median_ns| Stats {
+            min_ns: 0,
+            median_ns,
+            p95_ns: 0,
+        };
+
+        assert_eq!(stats(150).change_from(&stats(100)), 50.0);
+        assert_eq!(stats(50).change_from(&stats(100)), -50.0);
+    }
+}
@@ -0,0 +1,47 @@
+use aoc_core::DynSolution;
+
+pub struct Day {
+    pub number: u32,
+    pub solution: &'static dyn DynSolution,
+}
+
+impl Day {
+    const fn new(number: u32, solution: &'static dyn DynSolution) -> Self {
+        Self { number, solution }
+    }
+
+    /// Each day keeps its puzzle input next to its own sources.
+    pub fn default_input(&self) -> String {
+        self.input_file("data")
+    }
+
+    /// One of the day's inputs by name, e.g. `test1` for `src/test1.txt`.
+    pub fn input_file(&self, name: &str) -> String {
+        format!(
+            "{}/../day{:02}/src/{}.txt",
+            env!("CARGO_MANIFEST_DIR"),
+            self.number,
+            name
+        )
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new(1, &day01::Day01),
+    Day::new(2, &day02::Day02),
+    Day::new(3, &day03::Day03),
+    Day::new(4, &day04::Day04),
+    Day::new(5, &day05::Day05),
+    Day::new(6, &day06::Day06),
+    Day::new(7, &day07::Day07),
+    Day::new(8, &day08::Day08),
+    Day::new(9, &day09::Day09),
+    Day::new(10, &day10::Day10),
+    Day::new(11, &day11::Day11),
+    Day::new(12, &day12::Day12),
+    // new_day.sh adds days above this line
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
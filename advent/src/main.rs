@@ -0,0 +1,367 @@
+use answers::Answers;
+use aoc_core::Error;
+use bench::Baseline;
+use clap::{Parser, Subcommand};
+use days::Day;
+use std::{path::Path, process::ExitCode, time::Instant};
+
+mod answers;
+mod bench;
+mod days;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run one day, or every day with --all
+    Run {
+        #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u32>,
+        /// Only run this part, otherwise both are run
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Defaults to the day's src/data.txt
+        #[arg(long, conflicts_with = "all")]
+        input: Option<String>,
+        #[arg(long)]
+        all: bool,
+    },
+    /// List the days the runner knows about
+    List,
+    /// Check each day's answers against the ones recorded in answers.toml
+    Verify {
+        /// Only verify this day, otherwise every day is checked
+        #[arg(long)]
+        day: Option<u32>,
+        /// Defaults to answers.toml at the root of the workspace
+        #[arg(long)]
+        answers: Option<String>,
+    },
+    /// Time parse, part 1 and part 2 separately over many iterations
+    Bench {
+        /// Only benchmark this day, otherwise every day with an input is timed
+        #[arg(long)]
+        day: Option<u32>,
+        /// Defaults to the day's src/data.txt
+        #[arg(long, requires = "day")]
+        input: Option<String>,
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+        /// Defaults to bench_baseline.json at the root of the workspace
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Record this run as the new baseline for the days that were timed
+        #[arg(long)]
+        save: bool,
+        /// How much slower than the baseline's median counts as a regression, in percent
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            all,
+        } => {
+            let to_run = if all {
+                days::DAYS.iter().collect()
+            } else {
+                let number = day.unwrap();
+                match days::find(number) {
+                    Some(day) => vec![day],
+                    None => {
+                        eprintln!("Day {} has not been solved yet", number);
+                        return ExitCode::FAILURE;
+                    }
+                }
+            };
+
+            let mut failed = false;
+            for day in to_run {
+                let input = input.clone().unwrap_or_else(|| day.default_input());
+                if let Err(e) = run_day(day, part, &input) {
+                    eprintln!("Day {:02} failed\n{}", day.number, e);
+                    failed = true;
+                }
+            }
+
+            if failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::List => {
+            for day in days::DAYS {
+                let input = day.default_input();
+                if Path::new(&input).exists() {
+                    println!("Day {:02}", day.number);
+                } else {
+                    println!("Day {:02} (no input at {})", day.number, input);
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Verify { day, answers } => {
+            let answers_file = answers
+                .unwrap_or_else(|| format!("{}/../answers.toml", env!("CARGO_MANIFEST_DIR")));
+            let answers = match Answers::load(&answers_file) {
+                Ok(answers) => answers,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let Some(to_verify) = select_days(day) else {
+                return ExitCode::FAILURE;
+            };
+
+            let mut tally = Tally::default();
+            for day in to_verify {
+                for input in answers.inputs(day.number) {
+                    verify_day(day, &input, &answers, &mut tally);
+                }
+            }
+
+            println!(
+                "{} passed, {} failed, {} missing",
+                tally.passed, tally.failed, tally.missing
+            );
+            if tally.failed > 0 {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Bench {
+            day,
+            input,
+            iterations,
+            baseline,
+            save,
+            threshold,
+        } => {
+            let baseline_file = baseline.unwrap_or_else(|| {
+                format!("{}/../bench_baseline.json", env!("CARGO_MANIFEST_DIR"))
+            });
+            let mut baseline = match Baseline::load(&baseline_file) {
+                Ok(baseline) => baseline,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let Some(to_bench) = select_days(day) else {
+                return ExitCode::FAILURE;
+            };
+
+            let mut failed = false;
+            let mut regressions = 0;
+            for day_to_bench in to_bench {
+                let input = input
+                    .clone()
+                    .unwrap_or_else(|| day_to_bench.default_input());
+                // Asking for every day shouldn't fail just because some inputs haven't been downloaded
+                if day.is_none() && !Path::new(&input).exists() {
+                    println!(
+                        "Day {:02} skipped, no input at {}",
+                        day_to_bench.number, input
+                    );
+                    continue;
+                }
+
+                let timings = match bench_day(day_to_bench, &input, iterations as usize) {
+                    Ok(timings) => timings,
+                    Err(e) => {
+                        eprintln!("Day {:02} failed\n{}", day_to_bench.number, e);
+                        failed = true;
+                        continue;
+                    }
+                };
+
+                for (phase, stats) in bench::PHASES.into_iter().zip(timings) {
+                    let comparison = match baseline.get(day_to_bench.number, phase) {
+                        Some(previous) => {
+                            let change = stats.change_from(previous);
+                            if change > threshold {
+                                regressions += 1;
+                                format!("{:+.1}% vs baseline, REGRESSION", change)
+                            } else {
+                                format!("{:+.1}% vs baseline", change)
+                            }
+                        }
+                        None => "no baseline".to_string(),
+                    };
+                    println!(
+                        "Day {:02} {:<5}  {}  {}",
+                        day_to_bench.number, phase, stats, comparison
+                    );
+
+                    if save {
+                        baseline.insert(day_to_bench.number, phase, stats);
+                    }
+                }
+            }
+
+            if save {
+                match baseline.save(&baseline_file) {
+                    Ok(()) => println!("Saved baseline to {}", baseline_file),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        failed = true;
+                    }
+                }
+            }
+            if regressions > 0 {
+                println!(
+                    "{} phases regressed by more than {}%",
+                    regressions, threshold
+                );
+            }
+
+            if failed || regressions > 0 {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+    }
+}
+
+/// The one day asked for, or every day if none was. Reports unknown days and returns `None`.
+fn select_days(day: Option<u32>) -> Option<Vec<&'static Day>> {
+    match day {
+        Some(number) => match days::find(number) {
+            Some(day) => Some(vec![day]),
+            None => {
+                eprintln!("Day {} has not been solved yet", number);
+                None
+            }
+        },
+        None => Some(days::DAYS.iter().collect()),
+    }
+}
+
+fn run_day(day: &Day, part: Option<u8>, input: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let parsed = day
+        .solution
+        .parse_input(&aoc_core::read_input(input)?)
+        .map_err(|e| e.in_file(input))?;
+
+    if part.is_none_or(|p| p == 1) {
+        println!("Day {:02} Part 1: {}", day.number, parsed.part1()?);
+    }
+    if part.is_none_or(|p| p == 2) {
+        println!("Day {:02} Part 2: {}", day.number, parsed.part2()?);
+    }
+
+    Ok(())
+}
+
+/// Runs every part of `day` that has a recorded answer for `input`, printing a line per part.
+fn verify_day(day: &Day, input: &str, answers: &Answers, tally: &mut Tally) {
+    let label = format!("Day {:02} {}", day.number, input);
+    let expected = [1, 2].map(|part| answers.expected(day.number, input, part));
+    for (part, answer) in (1..=2).zip(expected) {
+        if answer.is_none() {
+            println!("{} part {}: missing, no answer recorded", label, part);
+            tally.missing += 1;
+        }
+    }
+    let recorded = expected.iter().flatten().count();
+    if recorded == 0 {
+        return;
+    }
+
+    let file = day.input_file(input);
+    if !Path::new(&file).exists() {
+        println!("{}: missing, no input at {}", label, file);
+        tally.missing += recorded;
+        return;
+    }
+
+    let start = Instant::now();
+    let parsed = match aoc_core::read_input(&file).and_then(|text| {
+        day.solution
+            .parse_input(&text)
+            .map_err(|e| Error::from(e.in_file(&file)))
+    }) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            println!("{}: FAIL\n{}", label, e);
+            tally.failed += recorded;
+            return;
+        }
+    };
+    println!("{}: parsed in {:.2?}", label, start.elapsed());
+
+    for (part, answer) in (1..=2).zip(expected) {
+        let Some(answer) = answer else {
+            continue;
+        };
+
+        let start = Instant::now();
+        let got = if part == 1 {
+            parsed.part1()
+        } else {
+            parsed.part2()
+        };
+        let elapsed = start.elapsed();
+
+        match got {
+            Ok(got) if got == answer => {
+                println!("{} part {}: pass in {:.2?}", label, part, elapsed);
+                tally.passed += 1;
+            }
+            Ok(got) => {
+                println!(
+                    "{} part {}: FAIL in {:.2?}, got {} but expected {}",
+                    label, part, elapsed, got, answer
+                );
+                tally.failed += 1;
+            }
+            Err(e) => {
+                println!("{} part {}: FAIL in {:.2?}\n{}", label, part, elapsed, e);
+                tally.failed += 1;
+            }
+        }
+    }
+}
+
+/// Times each phase of `day` on its own: parsing, then both parts against a single parsed input.
+fn bench_day(
+    day: &Day,
+    input: &str,
+    iterations: usize,
+) -> Result<[bench::Stats; 3], Box<dyn std::error::Error>> {
+    let text = aoc_core::read_input(input)?;
+
+    let (parsed, parse) = bench::time(iterations, || day.solution.parse_input(&text));
+    let parsed = parsed.map_err(|e| e.in_file(input))?;
+    // A part without an answer has nothing worth timing
+    let (answer, part1) = bench::time(iterations, || parsed.part1());
+    answer?;
+    let (answer, part2) = bench::time(iterations, || parsed.part2());
+    answer?;
+
+    Ok([parse, part1, part2])
+}
@@ -0,0 +1,29 @@
+use std::{error::Error, fmt::Display};
+
+/// What a part returns. Usually a number to print, but days where some inputs have no answer return a `Result`
+/// whose error says why, so the runner can report it rather than the part panicking.
+pub trait Answer {
+    fn into_result(self) -> Result<String, Box<dyn Error>>;
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_result(self) -> Result<String, Box<dyn Error>> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, String
+);
+
+impl<T: Display, E: Error + 'static> Answer for Result<T, E> {
+    fn into_result(self) -> Result<String, Box<dyn Error>> {
+        Ok(self?.to_string())
+    }
+}
@@ -0,0 +1,159 @@
+use std::fmt::Display;
+
+/// Counts and totals that can outgrow a machine integer, like joltages or timelines. With the `bigint` feature they
+/// are arbitrary precision, otherwise they are 128 bits wide and every step through [`Checked`] is checked.
+#[cfg(not(feature = "bigint"))]
+pub type Unsigned = u128;
+#[cfg(feature = "bigint")]
+pub type Unsigned = num_bigint::BigUint;
+
+/// Like [`Unsigned`], for answers that can go negative.
+#[cfg(not(feature = "bigint"))]
+pub type Signed = i128;
+#[cfg(feature = "bigint")]
+pub type Signed = num_bigint::BigInt;
+
+/// Finishes an error message about a [`Checked`] calculation that returned `None`, after what it was working out.
+#[cfg(not(feature = "bigint"))]
+pub const OVERFLOWED: &str =
+    "overflowed 128 bits, build with --features bigint for arbitrary precision";
+#[cfg(feature = "bigint")]
+pub const OVERFLOWED: &str = "is too large to work out";
+
+/// Arithmetic that returns `None` rather than wrapping or panicking. Machine integers give `None` once a result is
+/// out of their range, while big integers only do for results no integer has, like a quotient by zero or an
+/// unsigned difference below zero.
+pub trait Checked: Sized + Clone + Ord + Display + From<u8> {
+    fn try_add(&self, other: &Self) -> Option<Self>;
+    fn try_sub(&self, other: &Self) -> Option<Self>;
+    fn try_mul(&self, other: &Self) -> Option<Self>;
+    /// Rounds towards zero
+    fn try_div(&self, other: &Self) -> Option<Self>;
+    /// Has the sign of `self`
+    fn try_rem(&self, other: &Self) -> Option<Self>;
+    fn try_pow(&self, exponent: u32) -> Option<Self>;
+    fn to_u32(&self) -> Option<u32>;
+
+    /// The number whose decimal digits are `digits`, most significant first.
+    fn from_digits(digits: &[u8]) -> Option<Self> {
+        let ten = Self::from(10);
+        digits.iter().try_fold(Self::from(0), |number, digit| {
+            number.try_mul(&ten)?.try_add(&Self::from(*digit))
+        })
+    }
+}
+
+macro_rules! impl_checked {
+    ($($t:ty),*) => {
+        $(
+            impl Checked for $t {
+                fn try_add(&self, other: &Self) -> Option<Self> {
+                    self.checked_add(*other)
+                }
+
+                fn try_sub(&self, other: &Self) -> Option<Self> {
+                    self.checked_sub(*other)
+                }
+
+                fn try_mul(&self, other: &Self) -> Option<Self> {
+                    self.checked_mul(*other)
+                }
+
+                fn try_div(&self, other: &Self) -> Option<Self> {
+                    self.checked_div(*other)
+                }
+
+                fn try_rem(&self, other: &Self) -> Option<Self> {
+                    self.checked_rem(*other)
+                }
+
+                fn try_pow(&self, exponent: u32) -> Option<Self> {
+                    self.checked_pow(exponent)
+                }
+
+                fn to_u32(&self) -> Option<u32> {
+                    u32::try_from(*self).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_checked!(i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(feature = "bigint")]
+macro_rules! impl_checked_big {
+    ($($t:ty => $sub:expr),*) => {
+        $(
+            impl Checked for $t {
+                fn try_add(&self, other: &Self) -> Option<Self> {
+                    Some(self + other)
+                }
+
+                fn try_sub(&self, other: &Self) -> Option<Self> {
+                    ($sub)(self, other)
+                }
+
+                fn try_mul(&self, other: &Self) -> Option<Self> {
+                    Some(self * other)
+                }
+
+                fn try_div(&self, other: &Self) -> Option<Self> {
+                    (*other != Self::from(0_u8)).then(|| self / other)
+                }
+
+                fn try_rem(&self, other: &Self) -> Option<Self> {
+                    (*other != Self::from(0_u8)).then(|| self % other)
+                }
+
+                fn try_pow(&self, exponent: u32) -> Option<Self> {
+                    Some(self.pow(exponent))
+                }
+
+                fn to_u32(&self) -> Option<u32> {
+                    u32::try_from(self).ok()
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "bigint")]
+impl_checked_big!(
+    // Subtracting a larger BigUint would panic rather than go below zero
+    num_bigint::BigUint => |a: &num_bigint::BigUint, b| (a >= b).then(|| a - b),
+    num_bigint::BigInt => |a, b| Some(a - b)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked() {
+        let max = Unsigned::from_digits(&[9; 38]).unwrap();
+        #[cfg(not(feature = "bigint"))]
+        {
+            assert_eq!(Unsigned::from_digits(&[9; 40]), None);
+            assert_eq!(max.try_mul(&Unsigned::from(10_u8)), None);
+        }
+        #[cfg(feature = "bigint")]
+        assert_eq!(
+            max.try_mul(&Unsigned::from(10_u8)).unwrap().to_string(),
+            format!("{}0", "9".repeat(38))
+        );
+
+        assert_eq!(Unsigned::from(1_u8).try_sub(&Unsigned::from(2_u8)), None);
+        assert_eq!(Signed::from(1_u8).try_div(&Signed::from(0_u8)), None);
+        assert_eq!(Signed::from(7_u8).try_rem(&Signed::from(0_u8)), None);
+        assert_eq!(Signed::from(2_u8).try_pow(10), Some(Signed::from(1024)));
+        assert_eq!(
+            Signed::from(0_u8).try_sub(&Signed::from(3_u8)),
+            Some(Signed::from(-3))
+        );
+        assert_eq!(
+            Unsigned::from_digits(&[0, 4, 2]),
+            Some(Unsigned::from(42_u8))
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{Answer, ParseError, Solution};
+use std::error::Error;
+
+/// An object-safe view of a [`Solution`], so days with different input types can sit in one list.
+pub trait DynSolution {
+    fn parse_input(&self, input: &str) -> Result<Box<dyn ParsedInput>, ParseError>;
+}
+
+/// A day's parsed input, ready to answer either part. A part fails if the input has no answer for it.
+pub trait ParsedInput {
+    fn part1(&self) -> Result<String, Box<dyn Error>>;
+    fn part2(&self) -> Result<String, Box<dyn Error>>;
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> ParsedInput for Parsed<S> {
+    fn part1(&self) -> Result<String, Box<dyn Error>> {
+        S::part1(&self.0).into_result()
+    }
+
+    fn part2(&self) -> Result<String, Box<dyn Error>> {
+        S::part2(&self.0).into_result()
+    }
+}
+
+impl<S: Solution + 'static> DynSolution for S {
+    fn parse_input(&self, input: &str) -> Result<Box<dyn ParsedInput>, ParseError> {
+        Ok(Box::new(Parsed::<S>(S::parse(input)?)))
+    }
+}
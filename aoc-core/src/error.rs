@@ -0,0 +1,175 @@
+use std::{
+    fmt::{self, Display},
+    io,
+    str::FromStr,
+};
+
+/// Where and why an input failed to parse, with enough context to print a compiler-style diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    pub found: String,
+    pub expected: String,
+    source_line: String,
+}
+
+impl ParseError {
+    pub fn new(line: &Line, column: usize, found: &str, expected: &str) -> Self {
+        Self {
+            file: None,
+            line: line.number,
+            column,
+            found: found.to_string(),
+            expected: expected.to_string(),
+            source_line: line.text.to_string(),
+        }
+    }
+
+    /// The input stopped before something we needed, e.g. a missing second section.
+    pub fn end_of_input(input: &str, expected: &str) -> Self {
+        Self {
+            file: None,
+            line: input.lines().count() + 1,
+            column: 1,
+            found: "end of input".to_string(),
+            expected: expected.to_string(),
+            source_line: String::new(),
+        }
+    }
+
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = if self.found.is_empty() {
+            "nothing".to_string()
+        } else {
+            format!("{:?}", self.found)
+        };
+        writeln!(f, "error: expected {}, found {}", self.expected, found)?;
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.found.chars().count().max(1))
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Anything that can stop a day from getting as far as solving.
+#[derive(Debug)]
+pub enum Error {
+    Io { file: String, source: io::Error },
+    Parse(ParseError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { file, source } => write!(f, "error: failed to read {}: {}", file, source),
+            Error::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// A single line of input, which knows its own line number so errors can point back into it.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    /// 1-based line number
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at `part`, which must be a slice of this line's text.
+    pub fn error(&self, part: &str, expected: &str) -> ParseError {
+        let offset = (part.as_ptr() as usize)
+            .saturating_sub(self.text.as_ptr() as usize)
+            .min(self.text.len());
+        let column = self.text[..offset].chars().count() + 1;
+        ParseError::new(self, column, part, expected)
+    }
+
+    /// An error pointing just past the end of the line.
+    pub fn error_at_end(&self, expected: &str) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.parse::<T>().map_err(|_| self.error(part, expected))
+    }
+
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        delimiter: char,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(delimiter)
+            .ok_or_else(|| self.error(part, &format!("'{}'", delimiter)))
+    }
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(index, text)| Line {
+        number: index + 1,
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_points_at_part() {
+        let line = lines("L68\nR4x\n").nth(1).unwrap();
+        let error = line.parse::<i64>(&line.text[1..], "a number").unwrap_err();
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 2);
+        assert_eq!(error.found, "4x");
+        assert_eq!(
+            error.in_file("data.txt").to_string(),
+            "error: expected a number, found \"4x\"\n --> data.txt:2:2\n  |\n2 | R4x\n  |  ^^"
+        );
+    }
+
+    #[test]
+    fn test_error_columns_count_characters() {
+        let line = lines("é1").next().unwrap();
+        let error = line.split_once(&line.text[2..], ',').unwrap_err();
+
+        assert_eq!(error.column, 2);
+    }
+}
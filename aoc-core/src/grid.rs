@@ -0,0 +1,344 @@
+use crate::{Line, ParseError, lines};
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Which cells count as next to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    /// Up, down, left and right
+    Orthogonal,
+    /// Orthogonal plus the four diagonals
+    All,
+}
+
+/// What happens when a step walks off the side of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edges {
+    /// There is nothing past the edge
+    Bounded,
+    /// The grid is a torus, so stepping off one side comes back on the other
+    Wrapping,
+}
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const ALL: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one row per line, mapping each character to a cell. `cell` returns `None` for characters
+    /// that don't belong in the grid, which are reported as expecting `expected`. A grid needs at least one cell, so
+    /// an empty first row is an error as well.
+    pub fn parse(
+        input: &str,
+        cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let lines = lines(input).collect::<Vec<_>>();
+        if lines.is_empty() {
+            return Err(ParseError::end_of_input(input, "a grid"));
+        }
+        Self::parse_lines(&lines, cell, expected)
+    }
+
+    /// Like [`Grid::parse`], for a grid that is only part of the input.
+    pub fn parse_lines(
+        lines: &[Line],
+        mut cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let width = lines.first().map_or(0, |line| line.text.chars().count());
+        if let Some(first) = lines.first()
+            && width == 0
+        {
+            return Err(first.error_at_end(expected));
+        }
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for line in lines {
+            let mut row_width = 0;
+            for (i, c) in line.text.char_indices() {
+                cells.push(
+                    cell(c).ok_or_else(|| line.error(&line.text[i..i + c.len_utf8()], expected))?,
+                );
+                row_width += 1;
+            }
+            if row_width != width {
+                return Err(line.error(line.text, &format!("a row {} wide", width)));
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if self.contains(point) {
+            Some(&self.cells[point.y * self.width + point.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.y * self.width + point.x])
+        } else {
+            None
+        }
+    }
+
+    /// Every point in row-major order. This doesn't borrow the grid, so cells can be updated while walking it.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, and a zero width grid has no cells to chunk anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(self.height)
+    }
+
+    /// The point `dx`, `dy` away from `point`, or `None` if that is off a bounded grid.
+    pub fn offset(&self, point: Point, dx: isize, dy: isize, edges: Edges) -> Option<Point> {
+        match edges {
+            Edges::Bounded => {
+                let x = point.x.checked_add_signed(dx)?;
+                let y = point.y.checked_add_signed(dy)?;
+                let moved = Point::new(x, y);
+                self.contains(moved).then_some(moved)
+            }
+            Edges::Wrapping => {
+                if self.width == 0 || self.height == 0 {
+                    return None;
+                }
+                let x = (point.x as isize + dx).rem_euclid(self.width as isize) as usize;
+                let y = (point.y as isize + dy).rem_euclid(self.height as isize) as usize;
+                Some(Point::new(x, y))
+            }
+        }
+    }
+
+    pub fn neighbors(
+        &self,
+        point: Point,
+        neighborhood: Neighborhood,
+        edges: Edges,
+    ) -> impl Iterator<Item = Point> + use<'_, T> {
+        let offsets: &'static [(isize, isize)] = match neighborhood {
+            Neighborhood::Orthogonal => &ORTHOGONAL,
+            Neighborhood::All => &ALL,
+        };
+        offsets
+            .iter()
+            .filter_map(move |(dx, dy)| self.offset(point, *dx, *dy, edges))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    /// A quarter turn clockwise, so the left column becomes the top row.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, self.height - 1 - p.x)].clone()
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |p| {
+            self[Point::new(self.width - 1 - p.x, p.y)].clone()
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |p| {
+            self[Point::new(p.x, self.height - 1 - p.y)].clone()
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        assert!(self.contains(point), "{:?} is outside the grid", point);
+        &self.cells[point.y * self.width + point.x]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        assert!(self.contains(point), "{:?} is outside the grid", point);
+        &mut self.cells[point.y * self.width + point.x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |c| c.to_digit(10), "a digit").unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_views() {
+        let grid = parse_digits("123\n456\n");
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_ragged_rows() {
+        let error = Grid::parse("123\n45\n", |c| c.to_digit(10), "a digit").unwrap_err();
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.expected, "a row 3 wide");
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_rows() {
+        let error = Grid::parse("\n", |c| c.to_digit(10), "a digit").unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a digit");
+    }
+
+    #[test]
+    fn test_rotate_and_flip() {
+        let grid = parse_digits("12\n34\n56\n");
+
+        assert_eq!(grid.rotate_right(), parse_digits("531\n642\n"));
+        assert_eq!(grid.flip_horizontal(), parse_digits("21\n43\n65\n"));
+        assert_eq!(grid.flip_vertical(), parse_digits("56\n34\n12\n"));
+        assert_eq!(
+            grid.rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right(),
+            grid
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        let corner = Point::new(0, 0);
+
+        assert_eq!(
+            grid.neighbors(corner, Neighborhood::Orthogonal, Edges::Bounded)
+                .count(),
+            2
+        );
+        assert_eq!(
+            grid.neighbors(corner, Neighborhood::All, Edges::Bounded)
+                .count(),
+            3
+        );
+        assert!(
+            grid.neighbors(corner, Neighborhood::All, Edges::Wrapping)
+                .any(|p| p == Point::new(2, 2))
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use std::fmt::Debug;
+
+/// Integers that can be the ends of the ranges in an [`IntervalSet`].
+pub trait Integer: Copy + Ord + Debug {
+    fn checked_succ(self) -> Option<Self>;
+    fn checked_pred(self) -> Option<Self>;
+    /// How many integers there are in `start..=end`.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn checked_succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn checked_pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+/// A set of integers stored as inclusive ranges. The ranges are kept sorted, and ones that overlap or touch are
+/// merged as they go in, so there is only ever one way to store a set.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: vec![] }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The disjoint ranges making up the set, in order.
+    pub fn ranges(&self) -> &[(T, T)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many integers are in the set.
+    pub fn covered_len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|(start, end)| T::count(*start, *end))
+            .sum()
+    }
+
+    /// Adds `start..=end`, which does nothing if `start` is after `end`.
+    pub fn insert(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+
+        // The ranges that overlap or touch the new one are a run, which gets replaced by their union
+        let first = self.ranges.partition_point(|(_, existing_end)| {
+            existing_end
+                .checked_succ()
+                .is_some_and(|after| after < start)
+        });
+        let last = self.ranges.partition_point(|(existing_start, _)| {
+            existing_start
+                .checked_pred()
+                .is_none_or(|before| before <= end)
+        });
+        let mut merged = (start, end);
+        if first < last {
+            merged.0 = merged.0.min(self.ranges[first].0);
+            merged.1 = merged.1.max(self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.range_containing(value).is_some()
+    }
+
+    /// The range containing `value`, if any.
+    pub fn range_containing(&self, value: T) -> Option<(T, T)> {
+        let after = self.ranges.partition_point(|(start, _)| *start <= value);
+        (after > 0 && value <= self.ranges[after - 1].1).then(|| self.ranges[after - 1])
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for (start, end) in other.ranges.iter() {
+            union.insert(*start, *end);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Self::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            let start = a.0.max(b.0);
+            let end = a.1.min(b.1);
+            if start <= end {
+                // Both sets are already disjoint and sorted, so the overlaps are as well
+                intersection.ranges.push((start, end));
+            }
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        intersection
+    }
+
+    /// The integers in `start..=end` that are not in the set.
+    pub fn complement(&self, start: T, end: T) -> Self {
+        let mut gaps = Self::new();
+        if start > end {
+            return gaps;
+        }
+
+        let mut next = Some(start);
+        for (range_start, range_end) in self.ranges.iter() {
+            let Some(from) = next else {
+                break;
+            };
+            if *range_end < from {
+                continue;
+            }
+            if *range_start > end {
+                break;
+            }
+            if let Some(before) = range_start.checked_pred()
+                && from <= before
+            {
+                gaps.ranges.push((from, before));
+            }
+            next = range_end.checked_succ();
+        }
+        if let Some(from) = next
+            && from <= end
+        {
+            gaps.ranges.push((from, end));
+        }
+        gaps
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        match (self.ranges.first(), self.ranges.last()) {
+            (Some((start, _)), Some((_, end))) => {
+                self.intersection(&other.complement(*start, *end))
+            }
+            _ => Self::new(),
+        }
+    }
+}
+
+impl<T: Integer> FromIterator<(T, T)> for IntervalSet<T> {
+    /// Sorts the ranges first, so building a set from `n` ranges takes O(n log n).
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut ranges = iter
+            .into_iter()
+            .filter(|(start, end)| start <= end)
+            .collect::<Vec<_>>();
+        ranges.sort();
+
+        let mut set = Self::new();
+        for (start, end) in ranges {
+            match set.ranges.last_mut() {
+                Some(last) if last.1.checked_succ().is_none_or(|after| after >= start) => {
+                    last.1 = last.1.max(end)
+                }
+                _ => set.ranges.push((start, end)),
+            }
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rng;
+    use std::collections::BTreeSet;
+
+    fn members(set: &IntervalSet<i64>) -> BTreeSet<i64> {
+        set.ranges()
+            .iter()
+            .flat_map(|(start, end)| *start..=*end)
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_btreeset() {
+        let mut rng = Rng::new(5);
+        let mut next = |limit: u64| rng.below(limit);
+        let mut random_set = || {
+            let ranges = (0..next(8))
+                .map(|_| {
+                    let start = next(60) as i64 - 30;
+                    (start, start + next(8) as i64 - 1)
+                })
+                .collect::<Vec<_>>();
+            let mut inserted = IntervalSet::new();
+            for (start, end) in ranges.iter() {
+                inserted.insert(*start, *end);
+            }
+            let collected = ranges.iter().copied().collect::<IntervalSet<i64>>();
+            assert_eq!(inserted, collected);
+            collected
+        };
+
+        for _ in 0..500 {
+            let (a, b) = (random_set(), random_set());
+            let (a_members, b_members) = (members(&a), members(&b));
+
+            assert!(a.ranges().windows(2).all(|pair| pair[0].1 + 1 < pair[1].0));
+            assert_eq!(a.covered_len(), a_members.len() as u128);
+            assert!((-40..40).all(|value| a.contains(value) == a_members.contains(&value)));
+            assert_eq!(members(&a.union(&b)), &a_members | &b_members);
+            assert_eq!(members(&a.intersection(&b)), &a_members & &b_members);
+            assert_eq!(members(&a.difference(&b)), &a_members - &b_members);
+            assert_eq!(
+                members(&a.complement(-20, 20)),
+                (-20..=20)
+                    .filter(|value| !a_members.contains(value))
+                    .collect()
+            );
+        }
+    }
+
+    #[test]
+    fn test_extremes() {
+        let mut set = IntervalSet::new();
+        set.insert(i64::MAX - 1, i64::MAX);
+        set.insert(i64::MIN, i64::MIN + 1);
+        set.insert(i64::MIN + 2, 0);
+
+        assert_eq!(set.ranges(), &[(i64::MIN, 0), (i64::MAX - 1, i64::MAX)]);
+        assert_eq!(set.covered_len(), (1 << 63) + 3);
+        assert_eq!(
+            set.complement(i64::MIN, i64::MAX).ranges(),
+            &[(1, i64::MAX - 2)]
+        );
+        assert!(set.complement(i64::MIN, 0).is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+use clap::Parser;
+use std::{fmt::Display, fs, process::ExitCode};
+
+mod answer;
+mod checked;
+mod erased;
+mod error;
+mod grid;
+mod intervals;
+mod rng;
+
+pub use answer::Answer;
+pub use checked::{Checked, OVERFLOWED, Signed, Unsigned};
+pub use erased::{DynSolution, ParsedInput};
+pub use error::{Error, Line, ParseError, lines};
+pub use grid::{Edges, Grid, Neighborhood, Point};
+pub use intervals::{Integer, IntervalSet};
+pub use rng::Rng;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    #[arg(long, default_value = "")]
+    pub data_file: String,
+    #[arg(long)]
+    pub debug: bool,
+}
+
+impl Args {
+    /// The input file to run against, falling back to `src/data.txt` in the day's crate.
+    pub fn data_file(&self, manifest_dir: &str) -> String {
+        if self.data_file.is_empty() {
+            format!("{}/src/data.txt", manifest_dir)
+        } else {
+            self.data_file.clone()
+        }
+    }
+}
+
+/// A single day's puzzle: parse the input once, then answer both parts from it.
+pub trait Solution {
+    type Input;
+    type Output1: Answer;
+    type Output2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Output1;
+    fn part2(input: &Self::Input) -> Self::Output2;
+
+    fn parse_file(file: &str) -> Result<Self::Input, Error> {
+        Ok(Self::parse(&read_input(file)?).map_err(|e| e.in_file(file))?)
+    }
+}
+
+pub fn read_input(file: &str) -> Result<String, Error> {
+    fs::read_to_string(file).map_err(|source| Error::Io {
+        file: file.to_string(),
+        source,
+    })
+}
+
+/// The whole `main` for a day: read the arguments, parse the input and print both parts.
+pub fn run<S: Solution>(manifest_dir: &str) -> ExitCode {
+    let args = Args::parse();
+    let Some(input) = load::<S>(&args, manifest_dir) else {
+        return ExitCode::FAILURE;
+    };
+
+    match (
+        S::part1(&input).into_result(),
+        S::part2(&input).into_result(),
+    ) {
+        (Ok(part1), Ok(part2)) => print_answers(part1, part2),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Reads and parses the input `args` points at, printing why if that fails. For days whose `main` has flags of its
+/// own, with [`Args`] flattened into them.
+pub fn load<S: Solution>(args: &Args, manifest_dir: &str) -> Option<S::Input> {
+    match S::parse_file(&args.data_file(manifest_dir)) {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("{}", e);
+            None
+        }
+    }
+}
+
+pub fn print_answers(part1: impl Display, part2: impl Display) {
+    println!("Part1: {}", part1);
+
+    println!("Part 2: {}", part2);
+}
@@ -0,0 +1,46 @@
+/// A small seeded random number generator for making up inputs and for randomized tests. The same seed always gives
+/// the same numbers. It is a plain 64-bit linear congruential generator, so don't use it for anything that needs
+/// good randomness.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// A number from 0 up to but not including `limit`, or 0 if `limit` is 0.
+    pub fn below(&mut self, limit: u64) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        // Scaling the whole state down to the limit leans on its high bits, as the low bits of an LCG repeat quickly
+        ((self.state as u128 * limit as u128) >> 64) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.below(10)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        assert!(numbers(7).iter().all(|n| *n < 10));
+        assert_eq!(Rng::new(7).below(0), 0);
+
+        // Limits past 32 bits still reach the top of their range
+        let mut rng = Rng::new(7);
+        assert!((0..100).any(|_| rng.below(u64::MAX) > u64::MAX / 2));
+        assert!((0..100).all(|_| rng.below(10_u64.pow(15)) < 10_u64.pow(15)));
+    }
+}
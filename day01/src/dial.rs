@@ -0,0 +1,253 @@
+use itertools::Itertools;
+use std::{
+    fmt::{self, Display},
+    ops::Range,
+};
+
+/// The shape of the safe's dial: how many marks it has, where it starts and which mark we are counting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DialConfig {
+    pub size: i64,
+    pub start: i64,
+    pub target: i64,
+}
+
+impl Default for DialConfig {
+    fn default() -> Self {
+        Self {
+            size: 100,
+            start: 50,
+            target: 0,
+        }
+    }
+}
+
+/// How many of the clicks in `rotation` (negative is left) land on `mark`, starting from `position`.
+fn hits(position: i64, rotation: i64, mark: i64, size: i64) -> i64 {
+    // Clicks needed to reach the mark for the first time, going the way we are turning
+    let distance = if rotation >= 0 {
+        mark - position
+    } else {
+        position - mark
+    }
+    .rem_euclid(size);
+    let first = if distance == 0 { size } else { distance };
+
+    let (clicks, first) = (rotation.unsigned_abs(), first as u64);
+    if first > clicks {
+        0
+    } else {
+        // Only a dial with a single mark turned by i64::MIN clicks has more hits than an i64 holds
+        i64::try_from((clicks - first) / size as u64 + 1).unwrap_or(i64::MAX)
+    }
+}
+
+/// What a single rotation did to the dial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Step {
+    pub rotation: i64,
+    /// Where the dial points once the rotation is done
+    pub position: i64,
+    /// How many times the dial went round between the last mark and 0, in either direction
+    pub wraps: i64,
+    /// How many clicks left the dial pointing at the target, including the last one
+    pub target_hits: i64,
+}
+
+/// The safe's dial, turned one rotation at a time.
+#[derive(Debug, Clone)]
+pub struct Dial {
+    config: DialConfig,
+    position: i64,
+}
+
+impl Dial {
+    pub fn new(config: DialConfig) -> Self {
+        Self {
+            config,
+            position: config.start,
+        }
+    }
+
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    pub fn is_on_target(&self) -> bool {
+        self.position == self.config.target
+    }
+
+    pub fn turn(&mut self, rotation: i64) -> Step {
+        let size = self.config.size;
+        let target_hits = hits(self.position, rotation, self.config.target, size);
+        // Turning right wraps as it lands on 0, turning left as it lands on the last mark
+        let wrap_mark = if rotation >= 0 { 0 } else { size - 1 };
+        let wraps = hits(self.position, rotation, wrap_mark, size);
+        // Worked out wider, as the rotation can be anything an i64 holds
+        self.position = (self.position as i128 + rotation as i128).rem_euclid(size as i128) as i64;
+
+        Step {
+            rotation,
+            position: self.position,
+            wraps,
+            target_hits,
+        }
+    }
+}
+
+/// Sums over a run of steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Totals {
+    pub target_hits: i64,
+    pub wraps: i64,
+    /// Rotations that finished pointing at the target
+    pub landings: i64,
+}
+
+/// Every step of turning the dial through a list of rotations. Keeps running totals so queries over
+/// ranges of rotations don't have to walk them.
+#[derive(Debug, Clone)]
+pub struct Trace {
+    steps: Vec<Step>,
+    // Entry i is the totals for steps[..i], so there is one more of these than there are steps
+    totals_before: Vec<Totals>,
+}
+
+impl Trace {
+    pub fn new(rotations: &[i64], config: DialConfig) -> Self {
+        let mut dial = Dial::new(config);
+        let mut running = Totals::default();
+        let mut totals_before = vec![running];
+
+        let steps = rotations
+            .iter()
+            .map(|rotation| {
+                let step = dial.turn(*rotation);
+                running.target_hits += step.target_hits;
+                running.wraps += step.wraps;
+                running.landings += dial.is_on_target() as i64;
+                totals_before.push(running);
+                step
+            })
+            .collect();
+
+        Self {
+            steps,
+            totals_before,
+        }
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn position_after(&self, index: usize) -> Option<i64> {
+        self.steps.get(index).map(|step| step.position)
+    }
+
+    /// The index of the rotation during which the target is hit for the `n`th time, counting from 1.
+    pub fn nth_hit(&self, n: i64) -> Option<usize> {
+        if n < 1 {
+            return None;
+        }
+        let after = self
+            .totals_before
+            .partition_point(|totals| totals.target_hits < n);
+        (after < self.totals_before.len()).then(|| after - 1)
+    }
+
+    /// The indexes of the rotations that went past the target more than once.
+    pub fn repeated_hits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.steps.iter().positions(|step| step.target_hits > 1)
+    }
+
+    /// The totals for the rotations in `range`.
+    pub fn totals(&self, range: Range<usize>) -> Totals {
+        let before = self.totals_before[range.start];
+        let after = self.totals_before[range.end];
+        Totals {
+            target_hits: after.target_hits - before.target_hits,
+            wraps: after.wraps - before.wraps,
+            landings: after.landings - before.landings,
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = "index,rotation,position,wraps,target_hits\n".to_string();
+        for (index, step) in self.steps.iter().enumerate() {
+            csv += &format!(
+                "{},{},{},{},{}\n",
+                index, step.rotation, step.position, step.wraps, step.target_hits
+            );
+        }
+        csv
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>6} {:>12} {:>9} {:>10} {:>12}",
+            "index", "rotation", "position", "wraps", "target hits"
+        )?;
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>6} {:>12} {:>9} {:>10} {:>12}",
+                index, step.rotation, step.position, step.wraps, step.target_hits
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [i64; 10] = [-68, -30, 48, -5, 60, -55, -1, -99, 14, -82];
+
+    #[test]
+    fn test_trace_queries() {
+        let trace = Trace::new(&EXAMPLE, DialConfig::default());
+
+        assert_eq!(trace.position_after(0), Some(82));
+        assert_eq!(trace.position_after(2), Some(0));
+        assert_eq!(trace.position_after(10), None);
+        assert_eq!(trace.nth_hit(1), Some(0));
+        assert_eq!(trace.nth_hit(2), Some(2));
+        assert_eq!(trace.nth_hit(6), Some(9));
+        assert_eq!(trace.nth_hit(7), None);
+        assert_eq!(trace.repeated_hits().count(), 0);
+        assert_eq!(
+            trace.totals(0..10),
+            Totals {
+                target_hits: 6,
+                wraps: 6,
+                landings: 3
+            }
+        );
+        assert_eq!(trace.totals(3..5).target_hits, 1);
+    }
+
+    #[test]
+    fn test_wraps_and_repeated_hits() {
+        let trace = Trace::new(&[250, -250, 49], DialConfig::default());
+
+        assert_eq!(trace.steps()[0].wraps, 3);
+        assert_eq!(trace.steps()[1].wraps, 3);
+        assert_eq!(trace.repeated_hits().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(trace.totals(2..3), Totals::default());
+    }
+
+    #[test]
+    fn test_extreme_rotations() {
+        let trace = Trace::new(&[i64::MAX, i64::MIN], DialConfig::default());
+
+        assert_eq!(trace.position_after(0), Some(57));
+        assert_eq!(trace.position_after(1), Some(49));
+        assert_eq!(trace.steps()[0].target_hits, (i64::MAX - 50) / 100 + 1);
+        assert_eq!(trace.steps()[1].target_hits, i64::MAX / 100);
+    }
+}
@@ -0,0 +1,254 @@
+use aoc_core::{ParseError, Solution};
+use itertools::Itertools;
+
+mod dial;
+
+pub use dial::{Dial, DialConfig, Step, Totals, Trace};
+
+// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// struct Position {
+//     x: i64,
+//     y: i64,
+// }
+
+#[derive(Debug, Clone, Hash)]
+pub struct Input {
+    values: Vec<i64>,
+}
+
+/// The number of rotations that finish pointing at the target.
+pub fn part1_with_dial(input: &Input, config: &DialConfig) -> i64 {
+    let mut dial = Dial::new(*config);
+
+    input
+        .values
+        .iter()
+        .filter(|rotation| {
+            dial.turn(**rotation);
+            dial.is_on_target()
+        })
+        .count() as i64
+}
+
+/// The number of clicks, across every rotation, that leave the dial pointing at the target.
+pub fn part2_with_dial(input: &Input, config: &DialConfig) -> i64 {
+    let mut dial = Dial::new(*config);
+
+    input
+        .values
+        .iter()
+        .map(|rotation| dial.turn(*rotation).target_hits)
+        .sum()
+}
+
+pub fn trace(input: &Input, config: &DialConfig) -> Trace {
+    Trace::new(&input.values, *config)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Input;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn part1(input: &Input) -> i64 {
+        part1_with_dial(input, &DialConfig::default())
+    }
+
+    fn part2(input: &Input) -> i64 {
+        part2_with_dial(input, &DialConfig::default())
+    }
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        let lines = aoc_core::lines(input).collect_vec();
+
+        Ok(Input {
+            values: lines
+                .iter()
+                .map(|line| {
+                    let mut chars = line.text.chars();
+                    let val = match chars.next() {
+                        Some('L') => -1,
+                        Some('R') => 1,
+                        _ => {
+                            let direction = &line.text[..line.text.len() - chars.as_str().len()];
+                            return Err(line.error(direction, "'L' or 'R'"));
+                        }
+                    };
+                    // The direction is the sign, so the clicks can't have one of their own
+                    let clicks = chars.as_str();
+                    if !clicks.starts_with(|c: char| c.is_ascii_digit()) {
+                        return Err(line.error(clicks, "a number of clicks"));
+                    }
+                    Ok(line.parse::<i64>(clicks, "a number of clicks")? * val)
+                })
+                .collect::<Result<_, _>>()?,
+        })
+
+        /*
+         * Alternative implementations:
+         */
+
+        // Two sections separated by a newline
+        // Input {
+        //     first: lines
+        //         .iter()
+        //         .take_while(|line| !line.is_empty())
+        //         .map(|line| line.split_once('|').unwrap())
+        //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
+        //         .collect_vec(),
+        //     second: lines
+        //         .iter()
+        //         .skip_while(|line| !line.is_empty())
+        //         .filter(|line| !line.is_empty())
+        //         .map(|line| {
+        //             line.split(',')
+        //                 .map(|page| page.parse::<i64>().unwrap())
+        //                 .collect_vec()
+        //         })
+        //         .collect_vec(),
+        // }
+
+        // Creates a HashMap<char, Vec<Position>>
+        // let map_limits = Position {
+        //     x: lines[0].len() as i64,
+        //     y: lines.len() as i64,
+        // };
+
+        // Input {
+        //     antennas: lines
+        //         .into_iter()
+        //         .enumerate()
+        //         .flat_map(|(y, line)| {
+        //             line.chars()
+        //                 .enumerate()
+        //                 .filter(|(_, c)| *c != '.')
+        //                 .map(|(x, c)| {
+        //                     (
+        //                         c,
+        //                         Position {
+        //                             x: x as i64,
+        //                             y: y as i64,
+        //                         },
+        //                     )
+        //                 })
+        //                 .collect_vec()
+        //         })
+        //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
+        //         .chunk_by(|(c, _)| *c)
+        //         .into_iter()
+        //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
+        //         .collect(),
+        //     map_limits,
+        // }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+            Day01::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result1 = Day01::part1(&input);
+
+        assert_eq!(result1, 3);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+            Day01::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result2 = Day01::part2(&input);
+
+        assert_eq!(result2, 6);
+    }
+
+    /// The original part 2, one click at a time, to check the arithmetic against
+    fn part2_by_clicking(input: &Input, config: &DialConfig) -> i64 {
+        let mut num = config.start;
+
+        let mut count_of_zero = 0;
+        for i in input.values.iter() {
+            for _j in 0..i.abs() {
+                num = (num + i.signum()).rem_euclid(config.size);
+                if num == config.target {
+                    count_of_zero += 1;
+                }
+            }
+        }
+
+        count_of_zero
+    }
+
+    #[test]
+    fn test_part2_matches_clicking() {
+        // A small LCG is plenty to shake out off-by-ones
+        let mut rng = aoc_core::Rng::new(2025);
+        let mut next = |limit: u64| rng.below(limit) as i64;
+
+        for _ in 0..200 {
+            let size = next(20) + 1;
+            let config = DialConfig {
+                size,
+                start: next(size as u64),
+                target: next(size as u64),
+            };
+            let input = Input {
+                values: (0..next(30)).map(|_| next(121) - 60).collect(),
+            };
+
+            assert_eq!(
+                part2_with_dial(&input, &config),
+                part2_by_clicking(&input, &config),
+                "{:?} {:?}",
+                config,
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_part2_huge_rotations() {
+        let input = Input {
+            values: vec![5_000_000_050, -3_000_000_000, 1],
+        };
+
+        // 50 clicks reach 0 and then it passes 0 every 100 clicks, ending on it. Turning back from 0 takes a full
+        // 100 clicks to come round again.
+        assert_eq!(
+            part2_with_dial(&input, &DialConfig::default()),
+            50_000_001 + 30_000_000
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Day01::parse(input).unwrap_err();
+
+        let e = error("L68\nX30\n");
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 1, "'L' or 'R'")
+        );
+        // A trailing space is read as part of the number
+        let e = error("L68\nR4 \n");
+        assert_eq!(
+            (e.line, e.column, e.found.as_str(), e.expected.as_str()),
+            (2, 2, "4 ", "a number of clicks")
+        );
+        let e = error("L68\nR-5\n");
+        assert_eq!(
+            (e.line, e.column, e.found.as_str(), e.expected.as_str()),
+            (2, 2, "-5", "a number of clicks")
+        );
+        assert_eq!((error("L+5\n").line, error("L+5\n").column), (1, 2));
+        assert_eq!(
+            format!("{:?}", Day01::parse("L68\r\nR4\r\n").unwrap()),
+            format!("{:?}", Day01::parse("L68\nR4\n").unwrap())
+        );
+    }
+}
@@ -1,181 +1,59 @@
-use clap::Parser;
-use itertools::Itertools;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use clap::{Parser, ValueEnum};
+use day01::{Day01, DialConfig};
+use std::process::ExitCode;
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum TraceFormat {
+    Table,
+    Csv,
+}
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-    #[arg(long, default_value = "")]
-    data_file: String,
-    #[arg(long)]
-    debug: bool,
-}
-
-// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-// struct Position {
-//     x: i64,
-//     y: i64,
-// }
-
-#[derive(Debug, Clone, Hash)]
-struct Input {
-    values: Vec<i64>,
+struct Cli {
+    #[command(flatten)]
+    common: aoc_core::Args,
+    /// Print what every rotation did to the dial instead of the answers
+    #[arg(long, value_enum)]
+    trace: Option<TraceFormat>,
+    /// How many marks are on the dial
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(i64).range(1..))]
+    size: i64,
+    /// The mark the dial starts at
+    #[arg(long, default_value_t = 50)]
+    start: i64,
+    /// The mark to count
+    #[arg(long, default_value_t = 0)]
+    target: i64,
 }
 
-fn main() {
-    let args = Args::parse();
-    let data_file = if args.data_file.is_empty() {
-        format!("{}/src/data.txt", env!("CARGO_MANIFEST_DIR"))
-    } else {
-        args.data_file
+fn main() -> ExitCode {
+    let args = Cli::parse();
+    let config = DialConfig {
+        size: args.size,
+        start: args.start,
+        target: args.target,
     };
-
-    let input = parse(&data_file);
-
-    let result1 = part1(&input);
-    println!("Part1: {}", result1);
-
-    println!("Part 2: {}", part2(&input))
-}
-
-fn part1(input: &Input) -> i64 {
-    // We get the count of times we point at 0
-    // Start at 50
-    let mut num = 50;
-
-    let mut count_of_zero = 0;
-    for i in input.values.iter() {
-        num += i;
-        num = num.rem_euclid(100);
-        if num == 0 {
-            count_of_zero += 1;
-        }
-    }
-
-    count_of_zero
-}
-
-fn part2(input: &Input) -> i64 {
-    // We get the count of times we point at or pass 0
-    // Start at 50
-    let mut num = 50;
-
-    // No idea why using rem_euclid and div_euclid didn't work, so just count...
-    let mut count_of_zero = 0;
-    for i in input.values.iter() {
-        let abs_i = i.abs();
-        for _j in 0..abs_i {
-            num += i.signum();
-            if num == 0 {
-                count_of_zero += 1;
-            } else if num < 0 {
-                num += 100;
-            } else if num == 100 {
-                num -= 100;
-                count_of_zero += 1;
-            }
-        }
+    if !(0..config.size).contains(&config.start) || !(0..config.size).contains(&config.target) {
+        eprintln!(
+            "error: --start and --target must be marks on the dial, from 0 to {}",
+            config.size - 1
+        );
+        return ExitCode::FAILURE;
     }
 
-    count_of_zero
-}
-
-fn parse(file: &str) -> Input {
-    let file = File::open(file).expect("Failed to open file");
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader
-        .lines()
-        .map(|line| line.expect("Failed to read line"))
-        .collect();
-
-    Input {
-        values: lines
-            .iter()
-            .map(|line| {
-                let val = if line.starts_with("L") { -1 } else { 1 };
-                line[1..].parse::<i64>().unwrap() * val
-            })
-            .collect_vec(),
-    }
-
-    /*
-     * Alternative implementations:
-     */
-
-    // Two sections separated by a newline
-    // Input {
-    //     first: lines
-    //         .iter()
-    //         .take_while(|line| !line.is_empty())
-    //         .map(|line| line.split_once('|').unwrap())
-    //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
-    //         .collect_vec(),
-    //     second: lines
-    //         .iter()
-    //         .skip_while(|line| !line.is_empty())
-    //         .filter(|line| !line.is_empty())
-    //         .map(|line| {
-    //             line.split(',')
-    //                 .map(|page| page.parse::<i64>().unwrap())
-    //                 .collect_vec()
-    //         })
-    //         .collect_vec(),
-    // }
-
-    // Creates a HashMap<char, Vec<Position>>
-    // let map_limits = Position {
-    //     x: lines[0].len() as i64,
-    //     y: lines.len() as i64,
-    // };
-
-    // Input {
-    //     antennas: lines
-    //         .into_iter()
-    //         .enumerate()
-    //         .flat_map(|(y, line)| {
-    //             line.chars()
-    //                 .enumerate()
-    //                 .filter(|(_, c)| *c != '.')
-    //                 .map(|(x, c)| {
-    //                     (
-    //                         c,
-    //                         Position {
-    //                             x: x as i64,
-    //                             y: y as i64,
-    //                         },
-    //                     )
-    //                 })
-    //                 .collect_vec()
-    //         })
-    //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
-    //         .chunk_by(|(c, _)| *c)
-    //         .into_iter()
-    //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
-    //         .collect(),
-    //     map_limits,
-    // }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        let input = parse(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result1 = part1(&input);
+    let Some(input) = aoc_core::load::<Day01>(&args.common, env!("CARGO_MANIFEST_DIR")) else {
+        return ExitCode::FAILURE;
+    };
 
-        assert_eq!(result1, 3);
+    match args.trace {
+        Some(TraceFormat::Table) => print!("{}", day01::trace(&input, &config)),
+        Some(TraceFormat::Csv) => print!("{}", day01::trace(&input, &config).to_csv()),
+        None => aoc_core::print_answers(
+            day01::part1_with_dial(&input, &config),
+            day01::part2_with_dial(&input, &config),
+        ),
     }
 
-    #[test]
-    fn test_part2() {
-        let input = parse(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result2 = part2(&input);
-
-        assert_eq!(result2, 6);
-    }
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,348 @@
+//! Sums of invalid IDs over a range without visiting each one.
+//!
+//! An ID of `length` digits that is a block of `period` digits repeated is the block times a repunit-like multiplier,
+//! e.g. 123123 = 123 * 1001 and 121212 = 12 * 10101. Every block with the right number of digits gives an ID of the
+//! right length, so the IDs in a range with a given period are an arithmetic series in the block. Palindromes are
+//! similar: the first half picks the ID, and the mirrored second half sums digit by digit.
+
+/// Which IDs are invalid, looking at their digits in some base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// From 2 to 36
+    pub base: u32,
+    pub pattern: Pattern,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    /// A block of digits repeated to make up the whole ID
+    Repeated {
+        /// Exactly this many copies of the block, otherwise any number from two up
+        repetitions: Option<u32>,
+        min_period: u32,
+        max_period: Option<u32>,
+    },
+    /// Reads the same backwards
+    Palindrome,
+}
+
+impl Rule {
+    /// Part 1: a block repeated exactly twice in decimal.
+    pub const DOUBLED: Rule = Rule::repeated(10, Some(2));
+    /// Part 2: a block repeated any number of times in decimal.
+    pub const REPEATED: Rule = Rule::repeated(10, None);
+
+    pub const fn repeated(base: u32, repetitions: Option<u32>) -> Self {
+        Self {
+            base,
+            pattern: Pattern::Repeated {
+                repetitions,
+                min_period: 1,
+                max_period: None,
+            },
+        }
+    }
+
+    /// The sum of the invalid IDs in `start..=end`, or `None` if it overflows a u128.
+    pub fn sum(&self, start: u128, end: u128) -> Option<u128> {
+        assert!(
+            (2..=36).contains(&self.base),
+            "base {} is not from 2 to 36",
+            self.base
+        );
+        if start > end {
+            return Some(0);
+        }
+
+        let base = self.base as u128;
+        let mut sum: u128 = 0;
+        for length in digits(start, base)..=digits(end, base) {
+            let for_length = match self.pattern {
+                Pattern::Repeated {
+                    repetitions,
+                    min_period,
+                    max_period,
+                } => {
+                    let periods = (1..length)
+                        .filter(|period| length.is_multiple_of(*period))
+                        .filter(|period| repetitions.is_none_or(|times| length == period * times))
+                        .filter(|period| {
+                            *period >= min_period && max_period.is_none_or(|max| *period <= max)
+                        })
+                        .collect::<Vec<_>>();
+                    sum_any_period(start, end, length, &periods, base)?
+                }
+                Pattern::Palindrome => sum_palindromes(start, end, length, base)?,
+            };
+            sum = sum.checked_add(for_length)?;
+        }
+        Some(sum)
+    }
+}
+
+fn digits(mut n: u128, base: u128) -> u32 {
+    let mut count = 1;
+    while n >= base {
+        n /= base;
+        count += 1;
+    }
+    count
+}
+
+/// 1 followed by `period - 1` zeros, repeated `length / period` times, or `None` if that doesn't fit in a u128.
+fn multiplier(length: u32, period: u32, base: u128) -> Option<u128> {
+    let step = base.checked_pow(period)?;
+    let mut multiplier: u128 = 0;
+    let mut place: u128 = 1;
+    for i in 0..length / period {
+        multiplier = multiplier.checked_add(place)?;
+        if i + 1 < length / period {
+            place = place.checked_mul(step)?;
+        }
+    }
+    Some(multiplier)
+}
+
+/// The sum of `first..=last`, or `None` on overflow.
+fn series(first: u128, last: u128) -> Option<u128> {
+    let count = last - first + 1;
+    let total = first.checked_add(last)?;
+    // One of the two is even, so halve that one before multiplying
+    if count.is_multiple_of(2) {
+        (count / 2).checked_mul(total)
+    } else {
+        count.checked_mul(total / 2)
+    }
+}
+
+/// The sum of the `length` digit IDs in `start..=end` that repeat a block of `period` digits.
+fn sum_with_period(start: u128, end: u128, length: u32, period: u32, base: u128) -> Option<u128> {
+    let Some(multiplier) = multiplier(length, period, base) else {
+        // Even the smallest ID this long is bigger than a u128, so none of them are in the range
+        return Some(0);
+    };
+
+    // Blocks can't start with a 0, otherwise the ID would be shorter
+    let smallest_block = base.pow(period - 1);
+    let largest_block = base.checked_pow(period).map_or(u128::MAX, |p| p - 1);
+    let first = smallest_block.max(start.div_ceil(multiplier));
+    let last = largest_block.min(end / multiplier);
+    if first > last {
+        return Some(0);
+    }
+
+    series(first, last)?.checked_mul(multiplier)
+}
+
+fn mobius(mut n: u32) -> i32 {
+    let mut result = 1;
+    let mut factor = 2;
+    while factor * factor <= n {
+        if n.is_multiple_of(factor) {
+            n /= factor;
+            if n.is_multiple_of(factor) {
+                return 0;
+            }
+            result = -result;
+        }
+        factor += 1;
+    }
+    if n > 1 { -result } else { result }
+}
+
+/// The sum of the `length` digit IDs in `start..=end` that repeat with any of `periods`.
+fn sum_any_period(
+    start: u128,
+    end: u128,
+    length: u32,
+    periods: &[u32],
+    base: u128,
+) -> Option<u128> {
+    // An ID that repeats with period d also repeats with every multiple of d that divides the length, so summing
+    // each period on its own counts IDs more than once. Instead split the IDs by their shortest period, whose sum
+    // is the Möbius inversion of the per-period sums, and take every shortest period that divides one we want.
+    let mut added: u128 = 0;
+    let mut removed: u128 = 0;
+    for shortest in (1..length).filter(|g| periods.iter().any(|period| period.is_multiple_of(*g))) {
+        for period in (1..=shortest).filter(|period| shortest.is_multiple_of(*period)) {
+            let sign = mobius(shortest / period);
+            if sign == 0 {
+                continue;
+            }
+            let period_sum = sum_with_period(start, end, length, period, base)?;
+            if sign > 0 {
+                added = added.checked_add(period_sum)?;
+            } else {
+                removed = removed.checked_add(period_sum)?;
+            }
+        }
+    }
+    Some(added - removed)
+}
+
+/// The sum of digit `k` (counting from the least significant) of every number in `0..=n`.
+fn digit_sum_up_to(n: u128, k: u32, base: u128) -> Option<u128> {
+    let place = base.pow(k);
+    let cycle = place.checked_mul(base)?;
+    let count = n.checked_add(1)?;
+
+    // Each full cycle has every digit `place` times, and then the partial cycle has the smaller digits in full
+    let full = (count / cycle)
+        .checked_mul(place)?
+        .checked_mul(base * (base - 1) / 2)?;
+    let remainder = count % cycle;
+    let complete_digits = remainder / place;
+    let partial = place * (complete_digits * complete_digits.saturating_sub(1) / 2)
+        + (remainder % place) * complete_digits;
+    full.checked_add(partial)
+}
+
+/// The sum of the `length` digit palindromes in `start..=end`.
+fn sum_palindromes(start: u128, end: u128, length: u32, base: u128) -> Option<u128> {
+    // A palindrome is its first `half` digits, the head, followed by the first `length - half` of them mirrored
+    let half = length.div_ceil(2);
+    let shift = base.pow(length - half);
+    let palindrome = |head: u128| -> Option<u128> {
+        let mut tail = 0;
+        let mut rest = head / base.pow(2 * half - length);
+        for _ in 0..length - half {
+            tail = tail * base + rest % base;
+            rest /= base;
+        }
+        head.checked_mul(shift)?.checked_add(tail)
+    };
+
+    // Palindromes go up with their heads, so the ones in the range have a range of heads
+    let mut first = base.pow(half - 1);
+    if digits(start, base) == length {
+        first = start / shift;
+        if palindrome(first)? < start {
+            first += 1;
+        }
+    }
+    let mut last = base.checked_pow(half).map_or(u128::MAX, |p| p - 1);
+    if digits(end, base) == length {
+        last = end / shift;
+        if palindrome(last).is_none_or(|p| p > end) {
+            last -= 1;
+        }
+    }
+    if first > last {
+        return Some(0);
+    }
+
+    let mut sum = series(first, last)?.checked_mul(shift)?;
+    // Digit k of the head is copied to place half - 1 - k of the tail, for the digits that get mirrored
+    for k in (2 * half - length)..half {
+        let digit_sum = digit_sum_up_to(last, k, base)? - digit_sum_up_to(first - 1, k, base)?;
+        sum = sum.checked_add(digit_sum.checked_mul(base.pow(half - 1 - k))?)?;
+    }
+    Some(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_digits(mut n: u128, base: u128) -> Vec<u128> {
+        let mut digits = vec![];
+        loop {
+            digits.push(n % base);
+            n /= base;
+            if n == 0 {
+                break;
+            }
+        }
+        digits.reverse();
+        digits
+    }
+
+    fn is_invalid(id: u128, rule: &Rule) -> bool {
+        let digits = to_digits(id, rule.base as u128);
+        let length = digits.len() as u32;
+        match rule.pattern {
+            Pattern::Repeated {
+                repetitions,
+                min_period,
+                max_period,
+            } => (1..length)
+                .filter(|period| length.is_multiple_of(*period))
+                .filter(|period| repetitions.is_none_or(|times| length == period * times))
+                .filter(|period| {
+                    *period >= min_period && max_period.is_none_or(|max| *period <= max)
+                })
+                .any(|period| {
+                    digits
+                        .iter()
+                        .enumerate()
+                        .all(|(i, digit)| *digit == digits[i % period as usize])
+                }),
+            Pattern::Palindrome => digits.iter().eq(digits.iter().rev()),
+        }
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = aoc_core::Rng::new(2);
+        let mut next = |limit: u64| rng.below(limit) as u128;
+
+        for _ in 0..400 {
+            let base = [2, 3, 10, 16, 36][next(5) as usize];
+            let rule = Rule {
+                base,
+                pattern: match next(4) {
+                    0 => Pattern::Palindrome,
+                    1 => Pattern::Repeated {
+                        repetitions: None,
+                        min_period: next(3) as u32 + 1,
+                        max_period: Some(next(6) as u32 + 1),
+                    },
+                    _ => Pattern::Repeated {
+                        repetitions: [None, Some(2), Some(3)][next(3) as usize],
+                        min_period: 1,
+                        max_period: None,
+                    },
+                },
+            };
+            let start = next(200_000);
+            let end = start + next(5_000);
+
+            let expected = (start..=end).filter(|id| is_invalid(*id, &rule)).sum();
+            assert_eq!(
+                rule.sum(start, end),
+                Some(expected),
+                "{:?} {}-{}",
+                rule,
+                start,
+                end
+            );
+        }
+    }
+
+    #[test]
+    fn test_mobius() {
+        assert_eq!(
+            (1..=10).map(mobius).collect::<Vec<_>>(),
+            vec![1, -1, -1, 0, -1, 1, -1, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn test_huge_ranges() {
+        // Every 20 digit ID that is a 10 digit block twice: the blocks sum to 49499999995500000000
+        assert_eq!(
+            Rule::DOUBLED.sum(10_u128.pow(19), 10_u128.pow(20) - 1),
+            Some(49_499_999_995_500_000_000 * 10_000_000_001)
+        );
+        assert_eq!(Rule::REPEATED.sum(0, u128::MAX), None);
+        // The largest 128 bit palindrome in binary is all ones
+        assert_eq!(
+            Rule {
+                base: 2,
+                pattern: Pattern::Palindrome
+            }
+            .sum(u128::MAX, u128::MAX),
+            Some(u128::MAX)
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use aoc_core::{ParseError, Solution};
+use itertools::Itertools;
+use std::fmt::{self, Display};
+
+mod invalid_ids;
+
+pub use invalid_ids::{Pattern, Rule};
+
+// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// struct Position {
+//     x: i64,
+//     y: i64,
+// }
+
+#[derive(Debug, Clone, Hash)]
+pub struct Input {
+    values: Vec<(u128, u128)>,
+}
+
+/// Two of the input's ranges that share some IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Overlap {
+    /// Indexes of the two ranges, in the order they appear in the input
+    pub first: usize,
+    pub second: usize,
+    /// The IDs both ranges cover
+    pub start: u128,
+    pub end: u128,
+}
+
+impl Input {
+    pub fn ranges(&self) -> &[(u128, u128)] {
+        &self.values
+    }
+
+    /// The ranges sorted, with any that overlap or touch merged, so each ID is in at most one of them.
+    pub fn normalized(&self) -> Vec<(u128, u128)> {
+        let mut merged: Vec<(u128, u128)> = Vec::new();
+        for (start, end) in self
+            .values
+            .iter()
+            .filter(|(start, end)| start <= end)
+            .sorted()
+        {
+            match merged.last_mut() {
+                Some(last) if *start <= last.1.saturating_add(1) => last.1 = last.1.max(*end),
+                _ => merged.push((*start, *end)),
+            }
+        }
+        merged
+    }
+
+    /// Every pair of ranges that share IDs.
+    pub fn overlaps(&self) -> Vec<Overlap> {
+        let mut overlaps = Vec::new();
+        // Sweep the ranges by their start, keeping the ones that haven't ended yet
+        let mut open: Vec<usize> = Vec::new();
+        for index in (0..self.values.len()).sorted_by_key(|index| self.values[*index]) {
+            let (start, end) = self.values[index];
+            if start > end {
+                continue;
+            }
+            open.retain(|other| self.values[*other].1 >= start);
+            for other in open.iter() {
+                overlaps.push(Overlap {
+                    first: index.min(*other),
+                    second: index.max(*other),
+                    start,
+                    end: end.min(self.values[*other].1),
+                });
+            }
+            open.push(index);
+        }
+        overlaps.sort_by_key(|overlap| (overlap.first, overlap.second));
+        overlaps
+    }
+}
+
+/// The sum of invalid IDs got too big for a u128 once this range was added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SumOverflow {
+    pub start: u128,
+    pub end: u128,
+}
+
+impl Display for SumOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error: the sum of invalid IDs overflowed a u128 at range {}-{}",
+            self.start, self.end
+        )
+    }
+}
+
+impl std::error::Error for SumOverflow {}
+
+/// The sum of the IDs that break `rule`, counting each ID once even if it is in more than one range.
+pub fn sum_invalid(input: &Input, rule: &Rule) -> Result<u128, SumOverflow> {
+    input
+        .normalized()
+        .iter()
+        .try_fold(0_u128, |total, (start, end)| {
+            rule.sum(*start, *end)
+                .and_then(|sum| total.checked_add(sum))
+                .ok_or(SumOverflow {
+                    start: *start,
+                    end: *end,
+                })
+        })
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Input;
+    type Output1 = Result<u128, SumOverflow>;
+    type Output2 = Result<u128, SumOverflow>;
+
+    fn part1(input: &Input) -> Result<u128, SumOverflow> {
+        sum_invalid(input, &Rule::DOUBLED)
+    }
+
+    fn part2(input: &Input) -> Result<u128, SumOverflow> {
+        sum_invalid(input, &Rule::REPEATED)
+    }
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        let lines = aoc_core::lines(input).collect_vec();
+
+        Ok(Input {
+            values: lines
+                .iter()
+                .flat_map(|line| {
+                    line.text.split(',').map(|v| {
+                        let (start, end) = line.split_once(v, '-')?;
+                        Ok((
+                            line.parse::<u128>(start, "the start of a range")?,
+                            line.parse::<u128>(end, "the end of a range")?,
+                        ))
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+
+        /*
+         * Alternative implementations:
+         */
+
+        // Two sections separated by a newline
+        // Input {
+        //     first: lines
+        //         .iter()
+        //         .take_while(|line| !line.is_empty())
+        //         .map(|line| line.split_once('|').unwrap())
+        //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
+        //         .collect_vec(),
+        //     second: lines
+        //         .iter()
+        //         .skip_while(|line| !line.is_empty())
+        //         .filter(|line| !line.is_empty())
+        //         .map(|line| {
+        //             line.split(',')
+        //                 .map(|page| page.parse::<i64>().unwrap())
+        //                 .collect_vec()
+        //         })
+        //         .collect_vec(),
+        // }
+
+        // Creates a HashMap<char, Vec<Position>>
+        // let map_limits = Position {
+        //     x: lines[0].len() as i64,
+        //     y: lines.len() as i64,
+        // };
+
+        // Input {
+        //     antennas: lines
+        //         .into_iter()
+        //         .enumerate()
+        //         .flat_map(|(y, line)| {
+        //             line.chars()
+        //                 .enumerate()
+        //                 .filter(|(_, c)| *c != '.')
+        //                 .map(|(x, c)| {
+        //                     (
+        //                         c,
+        //                         Position {
+        //                             x: x as i64,
+        //                             y: y as i64,
+        //                         },
+        //                     )
+        //                 })
+        //                 .collect_vec()
+        //         })
+        //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
+        //         .chunk_by(|(c, _)| *c)
+        //         .into_iter()
+        //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
+        //         .collect(),
+        //     map_limits,
+        // }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+            Day02::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result1 = Day02::part1(&input);
+
+        assert_eq!(result1, Ok(1227775554));
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+            Day02::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result2 = Day02::part2(&input);
+
+        assert_eq!(result2, Ok(4174379265));
+    }
+
+    #[test]
+    fn test_overlapping_ranges() {
+        let input = Day02::parse("100-200,150-300,301-310,1000-1200,1100-1111\n").unwrap();
+
+        assert_eq!(input.normalized(), vec![(100, 310), (1000, 1200)]);
+        assert_eq!(
+            input.overlaps(),
+            vec![
+                Overlap {
+                    first: 0,
+                    second: 1,
+                    start: 150,
+                    end: 200
+                },
+                Overlap {
+                    first: 3,
+                    second: 4,
+                    start: 1100,
+                    end: 1111
+                }
+            ]
+        );
+        // 1010 and 1111 are only counted once, even though two ranges hold them
+        assert_eq!(
+            Day02::part1(&input),
+            Day02::part1(&Day02::parse("100-310,1000-1200\n").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Day02::parse(input).unwrap_err();
+
+        let e = error("11-22,95-x\n");
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (1, 10, "the end of a range")
+        );
+        let e = error("11-22,9522\n");
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 7, "'-'"));
+        // A trailing space is read as part of the last number
+        let e = error("11-22 \n");
+        assert_eq!(
+            (e.line, e.column, e.found.as_str(), e.expected.as_str()),
+            (1, 4, "22 ", "the end of a range")
+        );
+        assert_eq!(
+            format!("{:?}", Day02::parse("11-22,95-115\r\n").unwrap()),
+            format!("{:?}", Day02::parse("11-22,95-115\n").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_sum_overflow() {
+        let max = u128::MAX;
+        let palindromes = Rule {
+            base: 2,
+            pattern: Pattern::Palindrome,
+        };
+
+        // Each range's sum fits, but not once they are added up
+        let input = Day02::parse(&format!("1-1,{}-{}\n", max, max)).unwrap();
+        assert_eq!(
+            sum_invalid(&input, &palindromes),
+            Err(SumOverflow {
+                start: max,
+                end: max
+            })
+        );
+        // A single range whose sum doesn't fit
+        let input = Day02::parse(&format!("0-{}\n", max)).unwrap();
+        let error = Day02::part2(&input).unwrap_err();
+        assert_eq!(error, SumOverflow { start: 0, end: max });
+        assert!(error.to_string().contains("overflowed a u128 at range 0-"));
+    }
+}
@@ -1,230 +1,100 @@
 use clap::Parser;
-use itertools::Itertools;
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use day02::{Day02, Pattern, Rule};
+use std::process::ExitCode;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-    #[arg(long, default_value = "")]
-    data_file: String,
+struct Cli {
+    #[command(flatten)]
+    common: aoc_core::Args,
+    /// The base IDs are written in when looking for patterns, from 2 to 36
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(2..=36))]
+    base: u32,
+    /// Sum the IDs that are a block repeated exactly this many times, rather than answering both parts
+    #[arg(long, value_parser = clap::value_parser!(u32).range(2..))]
+    repetitions: Option<u32>,
+    /// Only count blocks at least this many digits long
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    min_period: Option<u32>,
+    /// Only count blocks at most this many digits long
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    max_period: Option<u32>,
+    /// Sum the IDs that are palindromes instead of repeated blocks
+    #[arg(long, conflicts_with_all = ["repetitions", "min_period", "max_period"])]
+    palindromes: bool,
+    /// List the ranges that share IDs before summing. Shared IDs are only ever counted once.
     #[arg(long)]
-    debug: bool,
+    report_overlaps: bool,
 }
 
-// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-// struct Position {
-//     x: i64,
-//     y: i64,
-// }
-
-#[derive(Debug, Clone, Hash)]
-struct Input {
-    values: Vec<(i64, i64)>,
-}
-
-fn main() {
-    let args = Args::parse();
-    let data_file = if args.data_file.is_empty() {
-        format!("{}/src/data.txt", env!("CARGO_MANIFEST_DIR"))
-    } else {
-        args.data_file
-    };
-
-    let input = parse(&data_file);
-
-    let result1 = part1(&input);
-    println!("Part1: {}", result1);
-
-    println!("Part 2: {}", part2(&input))
-}
-
-fn part1(input: &Input) -> i64 {
-    // NOTES:
-    // Odd number of digits is always valid and can be skipped
-    // For each number of digits you can just increment half the digits to find the duplicates
-    let mut sum = 0;
-    for (start, end) in input.values.iter() {
-        let start_str = start.to_string();
-        let mut current_length = start_str.len();
-
-        let end_str = end.to_string();
-        let end_length = end_str.len();
-
-        if current_length % 2 == 1 {
-            current_length += 1;
-        }
-
-        while current_length <= end_length {
-            // Numbers can't start with 0
-            let mut current_number = 10_i64
-                .pow(((current_length as u32) / 2) - 1)
-                .max(*start / 10_i64.pow((current_length as u32) / 2) - 1);
-            let end_number = (*end).min("9".repeat(end_length).parse::<i64>().unwrap());
-
-            while current_number <= end_number {
-                let current_str = current_number.to_string() + &current_number.to_string();
-                let new_num = current_str.parse::<i64>().unwrap();
-                if new_num > end_number {
-                    break;
-                }
-                if new_num >= *start {
-                    sum += new_num;
-                }
-                current_number += 1;
+impl Cli {
+    /// The rule picked by the flags, or `None` to answer the puzzle's two parts.
+    fn rule(&self) -> Option<Rule> {
+        let pattern = if self.palindromes {
+            Pattern::Palindrome
+        } else if self.repetitions.is_some()
+            || self.min_period.is_some()
+            || self.max_period.is_some()
+        {
+            Pattern::Repeated {
+                repetitions: self.repetitions,
+                min_period: self.min_period.unwrap_or(1),
+                max_period: self.max_period,
             }
-            current_length += 2;
-        }
+        } else {
+            return None;
+        };
+
+        Some(Rule {
+            base: self.base,
+            pattern,
+        })
     }
-
-    return sum;
 }
 
-fn part2(input: &Input) -> i64 {
-    // NOTES:
-    // Now we increment through a set of numbers which can be repeated
-    // We need to keep a history of recorded values to avoid duplicating them
-
-    let mut sum = 0;
-    for (start, end) in input.values.iter() {
-        let start_str = start.to_string();
-        let mut current_length = start_str.len();
-
-        let end_str = end.to_string();
-        let end_length = end_str.len();
-
-        let mut seen = HashSet::new();
-        while current_length <= end_length {
-            for sub_length in 1..=(current_length / 2) {
-                println!("sub_length: {}", sub_length);
-                if current_length % sub_length != 0 {
-                    continue;
-                }
-
-                let mut current_sub_sequence = 10_i64.pow((sub_length as u32) - 1);
-                while current_sub_sequence < 10_i64.pow(sub_length as u32) {
-                    let current_full = current_sub_sequence
-                        .to_string()
-                        .repeat(current_length / sub_length)
-                        .parse::<i64>()
-                        .unwrap();
+fn main() -> ExitCode {
+    let args = Cli::parse();
+    let Some(input) = aoc_core::load::<Day02>(&args.common, env!("CARGO_MANIFEST_DIR")) else {
+        return ExitCode::FAILURE;
+    };
 
-                    if current_full <= *end
-                        && current_full >= *start
-                        && !seen.contains(&current_full)
-                    {
-                        println!("{}", current_full);
-                        sum += current_full;
-                        seen.insert(current_full);
-                    }
-                    current_sub_sequence += 1;
-                }
-            }
-            current_length += 1;
+    if args.report_overlaps {
+        let ranges = input.ranges();
+        let overlaps = input.overlaps();
+        for overlap in overlaps.iter() {
+            let (first_start, first_end) = ranges[overlap.first];
+            let (second_start, second_end) = ranges[overlap.second];
+            println!(
+                "Range {} ({}-{}) overlaps range {} ({}-{}) on {}-{}",
+                overlap.first + 1,
+                first_start,
+                first_end,
+                overlap.second + 1,
+                second_start,
+                second_end,
+                overlap.start,
+                overlap.end
+            );
         }
+        println!("{} overlapping pairs of ranges", overlaps.len());
     }
 
-    return sum;
-}
-
-fn parse(file: &str) -> Input {
-    let file = File::open(file).expect("Failed to open file");
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader
-        .lines()
-        .map(|line| line.expect("Failed to read line"))
-        .collect();
-
-    Input {
-        values: lines
-            .iter()
-            .flat_map(|line| {
-                line.split(',').map(|v| {
-                    let (start, end) = v.split_once('-').unwrap();
-                    (start.parse::<i64>().unwrap(), end.parse::<i64>().unwrap())
-                })
-            })
-            .collect_vec(),
-    }
-
-    /*
-     * Alternative implementations:
-     */
-
-    // Two sections separated by a newline
-    // Input {
-    //     first: lines
-    //         .iter()
-    //         .take_while(|line| !line.is_empty())
-    //         .map(|line| line.split_once('|').unwrap())
-    //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
-    //         .collect_vec(),
-    //     second: lines
-    //         .iter()
-    //         .skip_while(|line| !line.is_empty())
-    //         .filter(|line| !line.is_empty())
-    //         .map(|line| {
-    //             line.split(',')
-    //                 .map(|page| page.parse::<i64>().unwrap())
-    //                 .collect_vec()
-    //         })
-    //         .collect_vec(),
-    // }
-
-    // Creates a HashMap<char, Vec<Position>>
-    // let map_limits = Position {
-    //     x: lines[0].len() as i64,
-    //     y: lines.len() as i64,
-    // };
-
-    // Input {
-    //     antennas: lines
-    //         .into_iter()
-    //         .enumerate()
-    //         .flat_map(|(y, line)| {
-    //             line.chars()
-    //                 .enumerate()
-    //                 .filter(|(_, c)| *c != '.')
-    //                 .map(|(x, c)| {
-    //                     (
-    //                         c,
-    //                         Position {
-    //                             x: x as i64,
-    //                             y: y as i64,
-    //                         },
-    //                     )
-    //                 })
-    //                 .collect_vec()
-    //         })
-    //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
-    //         .chunk_by(|(c, _)| *c)
-    //         .into_iter()
-    //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
-    //         .collect(),
-    //     map_limits,
-    // }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        let input = parse(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result1 = part1(&input);
-
-        assert_eq!(result1, 1227775554);
+    let sums = match args.rule() {
+        Some(rule) => day02::sum_invalid(&input, &rule).map(|sum| (sum, None)),
+        // The parts keep their own repetition rules, but still look at the IDs in the chosen base
+        None => day02::sum_invalid(&input, &Rule::repeated(args.base, Some(2))).and_then(|part1| {
+            let part2 = day02::sum_invalid(&input, &Rule::repeated(args.base, None))?;
+            Ok((part1, Some(part2)))
+        }),
+    };
+    match sums {
+        Ok((sum, None)) => println!("Sum of invalid IDs: {}", sum),
+        Ok((part1, Some(part2))) => aoc_core::print_answers(part1, part2),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
     }
 
-    #[test]
-    fn test_part2() {
-        let input = parse(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result2 = part2(&input);
-
-        assert_eq!(result2, 4174379265);
-    }
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,358 @@
+use aoc_core::Checked;
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display},
+};
+
+/// The integer joltages are worked out in. A u128 is enough for 38 batteries, past that build with the `bigint`
+/// feature.
+pub type Joltage = aoc_core::Unsigned;
+
+/// Why a bank couldn't be added to the total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JoltageError {
+    /// No batteries in the bank satisfy the selector. `bank` counts from 0.
+    NoSelection { bank: usize, batteries: usize },
+    /// The joltage got too big for [`Joltage`] while adding this bank
+    Overflow { bank: usize },
+}
+
+impl Display for JoltageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoltageError::NoSelection { bank, batteries } => write!(
+                f,
+                "error: bank {} has no way to turn on {} batteries that satisfies the constraints",
+                bank + 1,
+                batteries
+            ),
+            JoltageError::Overflow { bank } => write!(
+                f,
+                "error: joltage at bank {} {}",
+                bank + 1,
+                aoc_core::OVERFLOWED
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JoltageError {}
+
+/// The batteries turned on in one bank, in the order they appear.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Selection {
+    pub positions: Vec<usize>,
+    pub digits: Vec<u8>,
+}
+
+impl Selection {
+    fn from_positions(bank: &[u8], positions: Vec<usize>) -> Self {
+        Self {
+            digits: positions.iter().map(|position| bank[*position]).collect(),
+            positions,
+        }
+    }
+
+    /// The joltage the selected batteries produce, reading their digits as one number. `None` if it doesn't fit.
+    pub fn value(&self) -> Option<Joltage> {
+        Joltage::from_digits(&self.digits)
+    }
+}
+
+/// Adds the selections' joltages up, reporting the index of the bank that couldn't be selected or overflowed.
+pub fn checked_total(
+    selections: impl Iterator<Item = Option<Selection>>,
+    batteries: usize,
+) -> Result<Joltage, JoltageError> {
+    selections
+        .enumerate()
+        .try_fold(Joltage::from(0_u8), |total, (bank, selection)| {
+            let selection = selection.ok_or(JoltageError::NoSelection { bank, batteries })?;
+            selection
+                .value()
+                .and_then(|joltage| total.try_add(&joltage))
+                .ok_or(JoltageError::Overflow { bank })
+        })
+}
+
+/// Whether to look for the largest or the smallest joltage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Objective {
+    #[default]
+    Largest,
+    /// A leading 0 is allowed and just makes the joltage shorter. Ask for a leading digit of at least 1 to avoid it.
+    Smallest,
+}
+
+impl Objective {
+    /// Whether digit `a` beats digit `b`. Never true for equal digits, so ties keep the earliest battery.
+    fn prefers(self, a: u8, b: u8) -> bool {
+        match self {
+            Objective::Largest => a > b,
+            Objective::Smallest => a < b,
+        }
+    }
+}
+
+/// How to pick batteries in a bank: what to optimize for, and which selections are allowed at all.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Selector {
+    pub objective: Objective,
+    /// Never turn on two batteries next to each other
+    pub no_adjacent: bool,
+    /// The first battery turned on must have at least this digit
+    pub min_leading_digit: u8,
+    /// Positions in the bank, counting from 0, whose batteries can't be turned on
+    pub forbidden: BTreeSet<usize>,
+}
+
+impl Selector {
+    pub fn is_constrained(&self) -> bool {
+        self.no_adjacent || self.min_leading_digit > 0 || !self.forbidden.is_empty()
+    }
+
+    /// The `k` batteries that best meet the objective within the constraints, or `None` if no `k` batteries do.
+    /// Between equally good selections the one with the earliest batteries wins.
+    pub fn select(&self, bank: &[u8], k: usize) -> Option<Selection> {
+        if self.is_constrained() {
+            self.select_constrained(bank, k)
+        } else {
+            select_by_stack(bank, k, self.objective)
+        }
+    }
+
+    /// With constraints the stack can't tell whether dropping a battery leaves enough usable ones behind it, so
+    /// first work out the most batteries that can still be turned on from each position onwards. Then pick the
+    /// digits front to back, each time taking the best digit that leaves enough for the rest. Taking the earliest of
+    /// equal digits is always safe, as everything reachable after a later one is also reachable after it.
+    fn select_constrained(&self, bank: &[u8], k: usize) -> Option<Selection> {
+        // How far past a turned on battery the next one can be
+        let gap = if self.no_adjacent { 2 } else { 1 };
+        let allowed = |position: usize| !self.forbidden.contains(&position);
+
+        let mut most = vec![0; bank.len() + gap];
+        for position in (0..bank.len()).rev() {
+            let taking = if allowed(position) {
+                1 + most[position + gap]
+            } else {
+                0
+            };
+            most[position] = most[position + 1].max(taking);
+        }
+
+        let mut positions = Vec::with_capacity(k);
+        let mut from = 0;
+        for picked in 0..k {
+            let best = (from..bank.len())
+                .filter(|position| allowed(*position) && 1 + most[position + gap] >= k - picked)
+                .filter(|position| picked > 0 || bank[*position] >= self.min_leading_digit)
+                .reduce(|best, position| {
+                    if self.objective.prefers(bank[position], bank[best]) {
+                        position
+                    } else {
+                        best
+                    }
+                })?;
+            positions.push(best);
+            from = best + gap;
+        }
+
+        Some(Selection::from_positions(bank, positions))
+    }
+}
+
+/// The `k` batteries giving the largest joltage, or `None` if the bank doesn't have that many.
+pub fn select_largest(bank: &[u8], k: usize) -> Option<Selection> {
+    select_by_stack(bank, k, Objective::Largest)
+}
+
+/// Keeps a stack of picks in which the digits never get better. A better digit knocks worse ones off the top for as
+/// long as there are enough batteries left after it to still make `k`, so each battery is pushed and popped at most
+/// once.
+fn select_by_stack(bank: &[u8], k: usize, objective: Objective) -> Option<Selection> {
+    if bank.len() < k {
+        return None;
+    }
+
+    let mut can_drop = bank.len() - k;
+    let mut stack: Vec<usize> = Vec::with_capacity(bank.len());
+    for (position, digit) in bank.iter().enumerate() {
+        // Only strictly worse digits are dropped, so ties keep the earliest battery
+        while can_drop > 0
+            && stack
+                .last()
+                .is_some_and(|top| objective.prefers(*digit, bank[*top]))
+        {
+            stack.pop();
+            can_drop -= 1;
+        }
+        stack.push(position);
+    }
+    stack.truncate(k);
+
+    Some(Selection::from_positions(bank, stack))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_matches_every_combination() {
+        let banks: [&[u8]; 4] = [
+            &[9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1],
+            &[8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9],
+            &[2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8],
+            &[8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1],
+        ];
+
+        for bank in banks {
+            for k in 1..=6 {
+                let best = (0..bank.len())
+                    .combinations(k)
+                    .max_by_key(|positions| {
+                        (
+                            positions.iter().map(|p| bank[*p]).collect_vec(),
+                            // On a tie prefer the earliest batteries, like the selector does
+                            positions
+                                .iter()
+                                .map(|p| std::cmp::Reverse(*p))
+                                .collect_vec(),
+                        )
+                    })
+                    .unwrap();
+
+                assert_eq!(select_largest(bank, k).unwrap().positions, best);
+            }
+        }
+    }
+
+    /// The best of every allowed combination, by the same rules as [`Selector::select`].
+    fn brute_force(selector: &Selector, bank: &[u8], k: usize) -> Option<Vec<u8>> {
+        let allowed = (0..bank.len())
+            .combinations(k)
+            .filter(|positions| {
+                positions
+                    .iter()
+                    .all(|position| !selector.forbidden.contains(position))
+                    && positions
+                        .first()
+                        .is_none_or(|first| bank[*first] >= selector.min_leading_digit)
+                    && (!selector.no_adjacent
+                        || positions.iter().tuple_windows().all(|(a, b)| b - a > 1))
+            })
+            .map(|positions| positions.iter().map(|p| bank[*p]).collect_vec());
+        match selector.objective {
+            Objective::Largest => allowed.max(),
+            Objective::Smallest => allowed.min(),
+        }
+    }
+
+    #[test]
+    fn test_selectors_match_brute_force() {
+        let mut rng = aoc_core::Rng::new(3);
+        let mut next = |limit: u64| rng.below(limit) as usize;
+
+        for _ in 0..2000 {
+            let bank = (0..next(11) + 1).map(|_| next(10) as u8).collect_vec();
+            let k = next(6) + 1;
+            let selector = Selector {
+                objective: [Objective::Largest, Objective::Smallest][next(2)],
+                no_adjacent: next(2) == 0,
+                min_leading_digit: [0, 0, next(10) as u8][next(3)],
+                forbidden: (0..bank.len()).filter(|_| next(4) == 0).collect(),
+            };
+
+            let selection = selector.select(&bank, k);
+            assert_eq!(
+                selection.as_ref().map(|selection| selection.digits.clone()),
+                brute_force(&selector, &bank, k),
+                "{:?} {:?} {}",
+                selector,
+                bank,
+                k
+            );
+            if let Some(selection) = selection {
+                assert!(selection.positions.is_sorted());
+                assert!(
+                    selection
+                        .positions
+                        .iter()
+                        .all(|position| !selector.forbidden.contains(position))
+                );
+                assert!(
+                    !selector.no_adjacent
+                        || selection
+                            .positions
+                            .iter()
+                            .tuple_windows()
+                            .all(|(a, b)| b - a > 1)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unconstrained_smallest() {
+        let smallest = Selector {
+            objective: Objective::Smallest,
+            ..Selector::default()
+        };
+
+        assert_eq!(
+            smallest
+                .select(&[3, 1, 4, 1, 5, 9, 2, 6], 3)
+                .unwrap()
+                .digits,
+            vec![1, 1, 2]
+        );
+        // A leading 0 is fine unless the leading digit is constrained
+        assert_eq!(
+            smallest.select(&[5, 0, 7, 2], 2).unwrap().value(),
+            Some(Joltage::from(2_u32))
+        );
+        let leading = Selector {
+            min_leading_digit: 1,
+            ..smallest
+        };
+        assert_eq!(leading.select(&[5, 0, 7, 2], 2).unwrap().digits, vec![5, 0]);
+    }
+
+    #[test]
+    fn test_short_bank() {
+        assert_eq!(select_largest(&[1, 2], 3), None);
+        assert_eq!(
+            select_largest(&[1, 2], 2).unwrap().value(),
+            Some(Joltage::from(12_u32))
+        );
+    }
+
+    #[test]
+    fn test_long_selections() {
+        let bank = (0..60).map(|i| (i * 7 % 10) as u8).collect_vec();
+
+        let thirty = select_largest(&bank, 30).unwrap();
+        assert_eq!(
+            thirty.value().unwrap().to_string(),
+            thirty
+                .digits
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<String>()
+        );
+
+        let forty = select_largest(&bank, 40).unwrap();
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(forty.value(), None);
+        #[cfg(feature = "bigint")]
+        assert_eq!(
+            forty.value().unwrap().to_string(),
+            forty
+                .digits
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<String>()
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use aoc_core::{ParseError, Solution};
+use itertools::Itertools;
+
+mod joltage;
+
+pub use joltage::{Joltage, JoltageError, Objective, Selection, Selector, select_largest};
+
+// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// struct Position {
+//     x: i64,
+//     y: i64,
+// }
+
+#[derive(Debug, Clone, Hash)]
+pub struct Input {
+    values: Vec<Vec<u8>>,
+}
+
+impl Input {
+    pub fn banks(&self) -> &[Vec<u8>] {
+        &self.values
+    }
+}
+
+/// The best joltage each bank can make from `k` batteries under `selector`, added up.
+pub fn total_joltage(
+    input: &Input,
+    k: usize,
+    selector: &Selector,
+) -> Result<Joltage, JoltageError> {
+    joltage::checked_total(input.values.iter().map(|bank| selector.select(bank, k)), k)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Input;
+    type Output1 = Result<Joltage, JoltageError>;
+    type Output2 = Result<Joltage, JoltageError>;
+
+    // A bank with too few batteries, a blank line say, is reported rather than skipped
+    fn part1(input: &Input) -> Result<Joltage, JoltageError> {
+        total_joltage(input, 2, &Selector::default())
+    }
+
+    fn part2(input: &Input) -> Result<Joltage, JoltageError> {
+        total_joltage(input, 12, &Selector::default())
+    }
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        let lines = aoc_core::lines(input).collect_vec();
+
+        Ok(Input {
+            values: lines
+                .iter()
+                .map(|line| {
+                    line.text
+                        .char_indices()
+                        .map(|(i, v)| {
+                            v.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                                line.error(
+                                    &line.text[i..i + v.len_utf8()],
+                                    "a battery joltage digit",
+                                )
+                            })
+                        })
+                        .collect()
+                })
+                .collect::<Result<_, _>>()?,
+        })
+
+        /*
+         * Alternative implementations:
+         */
+
+        // Two sections separated by a newline
+        // Input {
+        //     first: lines
+        //         .iter()
+        //         .take_while(|line| !line.is_empty())
+        //         .map(|line| line.split_once('|').unwrap())
+        //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
+        //         .collect_vec(),
+        //     second: lines
+        //         .iter()
+        //         .skip_while(|line| !line.is_empty())
+        //         .filter(|line| !line.is_empty())
+        //         .map(|line| {
+        //             line.split(',')
+        //                 .map(|page| page.parse::<i64>().unwrap())
+        //                 .collect_vec()
+        //         })
+        //         .collect_vec(),
+        // }
+
+        // Creates a HashMap<char, Vec<Position>>
+        // let map_limits = Position {
+        //     x: lines[0].len() as i64,
+        //     y: lines.len() as i64,
+        // };
+
+        // Input {
+        //     antennas: lines
+        //         .into_iter()
+        //         .enumerate()
+        //         .flat_map(|(y, line)| {
+        //             line.chars()
+        //                 .enumerate()
+        //                 .filter(|(_, c)| *c != '.')
+        //                 .map(|(x, c)| {
+        //                     (
+        //                         c,
+        //                         Position {
+        //                             x: x as i64,
+        //                             y: y as i64,
+        //                         },
+        //                     )
+        //                 })
+        //                 .collect_vec()
+        //         })
+        //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
+        //         .chunk_by(|(c, _)| *c)
+        //         .into_iter()
+        //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
+        //         .collect(),
+        //     map_limits,
+        // }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+            Day03::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result1 = Day03::part1(&input);
+
+        assert_eq!(result1, Ok(Joltage::from(357_u32)));
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+            Day03::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result2 = Day03::part2(&input);
+
+        assert_eq!(result2, Ok(Joltage::from(3121910778619_u64)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Day03::parse(input).unwrap_err();
+
+        let e = error("987\n98a7\n");
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 3, "a battery joltage digit")
+        );
+        let e = error("987 \n");
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 4, " "));
+        assert_eq!(
+            format!("{:?}", Day03::parse("987\r\n811\r\n").unwrap()),
+            format!("{:?}", Day03::parse("987\n811\n").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_short_banks() {
+        // The blank line is a bank with no batteries, which can't turn on even two
+        let input = Day03::parse("987654321111\n\n811111111119\n").unwrap();
+        let error = Day03::part1(&input).unwrap_err();
+
+        assert_eq!(
+            error,
+            JoltageError::NoSelection {
+                bank: 1,
+                batteries: 2
+            }
+        );
+        assert!(
+            error
+                .to_string()
+                .contains("bank 2 has no way to turn on 2 batteries")
+        );
+    }
+}
@@ -1,175 +1,102 @@
 use clap::Parser;
-use itertools::Itertools;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use day03::{Day03, Objective, Selector};
+use std::process::ExitCode;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-    #[arg(long, default_value = "")]
-    data_file: String,
+struct Cli {
+    #[command(flatten)]
+    common: aoc_core::Args,
+    /// Turn on this many batteries per bank, rather than answering both parts
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    digits: Option<u32>,
+    /// Look for the smallest joltage instead of the largest
     #[arg(long)]
-    debug: bool,
-}
-
-// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-// struct Position {
-//     x: i64,
-//     y: i64,
-// }
-
-#[derive(Debug, Clone, Hash)]
-struct Input {
-    values: Vec<Vec<i64>>,
-}
-
-fn main() {
-    let args = Args::parse();
-    let data_file = if args.data_file.is_empty() {
-        format!("{}/src/data.txt", env!("CARGO_MANIFEST_DIR"))
-    } else {
-        args.data_file
-    };
-
-    let input = parse(&data_file);
-
-    let result1 = part1(&input);
-    println!("Part1: {}", result1);
-
-    println!("Part 2: {}", part2(&input))
-}
-
-fn part1(input: &Input) -> i64 {
-    let mut sum = 0;
-
-    for bank in input.values.iter() {
-        let max_first = bank.iter().take(bank.len() - 1).max().unwrap();
-        let first_index = bank.iter().position(|b| *b == *max_first).unwrap();
-        let max_second = bank.iter().skip(first_index + 1).max().unwrap();
-
-        sum += (max_first * 10) + max_second;
-    }
-
-    return sum;
+    smallest: bool,
+    /// Never turn on two batteries next to each other
+    #[arg(long)]
+    no_adjacent: bool,
+    /// The first battery turned on in each bank must have at least this digit
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=9))]
+    min_leading: u8,
+    /// Positions in each bank, counting from 1, whose batteries can't be turned on
+    #[arg(long, value_delimiter = ',', value_parser = clap::value_parser!(u64).range(1..))]
+    forbid: Vec<u64>,
 }
 
-fn part2(input: &Input) -> i64 {
-    let mut sum = 0;
-
-    for bank in input.values.iter() {
-        let mut last_index = 0;
-        for i in 0..12 {
-            let to_consider = bank
+impl Cli {
+    fn selector(&self) -> Selector {
+        Selector {
+            objective: if self.smallest {
+                Objective::Smallest
+            } else {
+                Objective::Largest
+            },
+            no_adjacent: self.no_adjacent,
+            min_leading_digit: self.min_leading,
+            forbidden: self
+                .forbid
                 .iter()
-                .skip(last_index)
-                .take(bank.len() - last_index - (11 - i))
-                .collect_vec();
-            let max = **to_consider.iter().max().unwrap();
-            last_index = to_consider.iter().position(|b| **b == max).unwrap() + last_index + 1;
-
-            sum += 10_i64.pow(11 - i as u32) * max;
+                .map(|position| *position as usize - 1)
+                .collect(),
         }
     }
-
-    return sum;
 }
 
-fn parse(file: &str) -> Input {
-    let file = File::open(file).expect("Failed to open file");
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader
-        .lines()
-        .map(|line| line.expect("Failed to read line"))
-        .collect();
-
-    Input {
-        values: lines
-            .iter()
-            .map(|line| {
-                line.chars()
-                    .map(|v| v.to_string().parse::<i64>().unwrap())
-                    .collect()
-            })
-            .collect_vec(),
-    }
-
-    /*
-     * Alternative implementations:
-     */
-
-    // Two sections separated by a newline
-    // Input {
-    //     first: lines
-    //         .iter()
-    //         .take_while(|line| !line.is_empty())
-    //         .map(|line| line.split_once('|').unwrap())
-    //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
-    //         .collect_vec(),
-    //     second: lines
-    //         .iter()
-    //         .skip_while(|line| !line.is_empty())
-    //         .filter(|line| !line.is_empty())
-    //         .map(|line| {
-    //             line.split(',')
-    //                 .map(|page| page.parse::<i64>().unwrap())
-    //                 .collect_vec()
-    //         })
-    //         .collect_vec(),
-    // }
-
-    // Creates a HashMap<char, Vec<Position>>
-    // let map_limits = Position {
-    //     x: lines[0].len() as i64,
-    //     y: lines.len() as i64,
-    // };
-
-    // Input {
-    //     antennas: lines
-    //         .into_iter()
-    //         .enumerate()
-    //         .flat_map(|(y, line)| {
-    //             line.chars()
-    //                 .enumerate()
-    //                 .filter(|(_, c)| *c != '.')
-    //                 .map(|(x, c)| {
-    //                     (
-    //                         c,
-    //                         Position {
-    //                             x: x as i64,
-    //                             y: y as i64,
-    //                         },
-    //                     )
-    //                 })
-    //                 .collect_vec()
-    //         })
-    //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
-    //         .chunk_by(|(c, _)| *c)
-    //         .into_iter()
-    //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
-    //         .collect(),
-    //     map_limits,
-    // }
+/// The bank with the selected batteries in bold green.
+fn highlight(bank: &[u8], positions: &[usize]) -> String {
+    bank.iter()
+        .enumerate()
+        .map(|(position, digit)| {
+            if positions.contains(&position) {
+                format!("\x1b[1;32m{}\x1b[0m", digit)
+            } else {
+                digit.to_string()
+            }
+        })
+        .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        let input = parse(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result1 = part1(&input);
+fn main() -> ExitCode {
+    let args = Cli::parse();
+    let Some(input) = aoc_core::load::<Day03>(&args.common, env!("CARGO_MANIFEST_DIR")) else {
+        return ExitCode::FAILURE;
+    };
 
-        assert_eq!(result1, 357);
+    let selector = args.selector();
+    let digits = args.digits.map(|k| vec![k as usize]).unwrap_or(vec![2, 12]);
+
+    if args.common.debug {
+        for k in digits.iter() {
+            println!("Picking {} batteries per bank:", k);
+            for bank in input.banks() {
+                match selector.select(bank, *k) {
+                    Some(selection) => {
+                        let value = selection
+                            .value()
+                            .map_or("overflowed".to_string(), |value| value.to_string());
+                        println!("{}  {}", highlight(bank, &selection.positions), value);
+                    }
+                    None => println!("{}  no selection", highlight(bank, &[])),
+                }
+            }
+        }
     }
 
-    #[test]
-    fn test_part2() {
-        let input = parse(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result2 = part2(&input);
-
-        assert_eq!(result2, 3121910778619);
+    let totals = match args.digits {
+        Some(k) => day03::total_joltage(&input, k as usize, &selector).map(|total| vec![total]),
+        None => day03::total_joltage(&input, 2, &selector)
+            .and_then(|part1| Ok(vec![part1, day03::total_joltage(&input, 12, &selector)?])),
+    };
+    match totals.as_deref() {
+        Ok([total]) => println!("Total joltage: {}", total),
+        Ok([part1, part2]) => aoc_core::print_answers(part1, part2),
+        Ok(_) => unreachable!("one total per digit count"),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
     }
+
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,308 @@
+use crate::{Rule, Spot, rule::Reach};
+use aoc_core::{Grid, Point};
+use clap::ValueEnum;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+/// Whether rolls removed during a wave already count as gone for the rest of that wave.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Semantics {
+    /// Each wave is decided from a snapshot of the grid taken before it starts
+    #[default]
+    Generation,
+    /// Each wave sweeps the grid in reading order, removing rolls as it goes, so a roll can be freed up by one
+    /// removed earlier in the same sweep
+    Cascade,
+}
+
+/// What became of a spot in the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fate {
+    Empty,
+    /// The roll was removed in this wave, counting from 0
+    Removed(usize),
+    /// The roll was never accessible
+    Left,
+}
+
+/// How many rolls each wave removed, until one removed nothing, and the wave that removed each roll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Waves {
+    pub removed: Vec<usize>,
+    pub fates: Grid<Fate>,
+}
+
+impl Waves {
+    fn new(grid: &Grid<Spot>) -> Self {
+        Self {
+            removed: vec![],
+            fates: grid.map(|spot| match spot {
+                Spot::Roll => Fate::Left,
+                Spot::Empty => Fate::Empty,
+            }),
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.removed.iter().sum()
+    }
+}
+
+/// How many rolls are around a roll, kept for every spot of the grid. The largest value marks a spot with no roll in
+/// it, or none any more.
+trait Count: Copy + Eq {
+    const GONE: Self;
+    fn new(count: usize) -> Self;
+    fn get(self) -> usize;
+}
+
+macro_rules! impl_count {
+    ($($t:ty),*) => {
+        $(
+            impl Count for $t {
+                const GONE: Self = <$t>::MAX;
+
+                fn new(count: usize) -> Self {
+                    count as $t
+                }
+
+                fn get(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_count!(u8, usize);
+
+/// The grid part way through eroding, as how many rolls are around every roll that is still there.
+struct Erosion<'a, C> {
+    counts: Grid<C>,
+    reach: Reach,
+    /// Only filled in when the caller wants to know which wave removed each roll
+    fates: Option<&'a mut Grid<Fate>>,
+    wave: usize,
+    // Reused for every removal, since the counts can't be changed while walking around them
+    neighbors: Vec<Point>,
+}
+
+impl<C: Count> Erosion<'_, C> {
+    /// Removes the roll at `point` in the current wave, calling `freed` for each roll that just became accessible
+    /// because of it. Rolls that were already accessible are queued already, so they aren't passed on again.
+    fn remove(&mut self, point: Point, mut freed: impl FnMut(Point)) {
+        self.counts[point] = C::GONE;
+        if let Some(fates) = self.fates.as_deref_mut() {
+            fates[point] = Fate::Removed(self.wave);
+        }
+
+        self.neighbors.clear();
+        self.neighbors
+            .extend(self.reach.around(&self.counts, point));
+        for neighbor in self.neighbors.iter() {
+            let count = self.counts[*neighbor];
+            if count != C::GONE {
+                self.counts[*neighbor] = C::new(count.get() - 1);
+                if count.get() == self.reach.limit {
+                    freed(*neighbor);
+                }
+            }
+        }
+    }
+}
+
+/// The rolls removed in every wave, each with its wave filled in `fates` when given.
+pub(crate) fn waves(grid: &Grid<Spot>, rule: &Rule, semantics: Semantics) -> Waves {
+    let mut waves = Waves::new(grid);
+    waves.removed = erode(grid, rule, semantics, Some(&mut waves.fates));
+    waves
+}
+
+/// Keeps removing rolls that are accessible by `rule` until none are left, returning how many each wave removed. The
+/// wave that removed each roll is written to `fates` if there is one.
+///
+/// Counts every roll's neighbours once up front and then only updates the counts around the rolls that go, so each
+/// roll is looked at a bounded number of times. With generation semantics a roll that becomes accessible joins the
+/// next wave whatever order the current one is removed in, so each wave is a plain queue and the work is linear in
+/// the size of the grid. Cascade semantics need each wave removed in reading order, since a roll freed up ahead of
+/// the sweep joins the current wave and one behind it the next, so their waves are kept in a heap instead. Removing
+/// rolls only ever makes others more accessible, so both semantics remove the same rolls in the end, just in
+/// different waves.
+///
+/// The counts take a byte a spot whenever the neighbourhood is small enough, which it is for the puzzle's rule, so
+/// huge grids only need about as much again as the grid itself.
+pub(crate) fn erode(
+    grid: &Grid<Spot>,
+    rule: &Rule,
+    semantics: Semantics,
+    fates: Option<&mut Grid<Fate>>,
+) -> Vec<usize> {
+    let reach = rule.reach();
+    if reach.most() < u8::MAX as usize {
+        erode_with::<u8>(grid, reach, semantics, fates)
+    } else {
+        erode_with::<usize>(grid, reach, semantics, fates)
+    }
+}
+
+fn erode_with<C: Count>(
+    grid: &Grid<Spot>,
+    reach: Reach,
+    semantics: Semantics,
+    fates: Option<&mut Grid<Fate>>,
+) -> Vec<usize> {
+    let counts = Grid::from_fn(grid.width(), grid.height(), |point| match grid[point] {
+        Spot::Roll => C::new(reach.rolls_around(grid, point)),
+        Spot::Empty => C::GONE,
+    });
+    // In reading order, since that is the order the grid is walked in
+    let mut wave = counts
+        .iter()
+        .filter(|(_, count)| **count != C::GONE && count.get() < reach.limit)
+        .map(|(point, _)| point)
+        .collect::<VecDeque<_>>();
+    let mut erosion = Erosion {
+        counts,
+        reach,
+        fates,
+        wave: 0,
+        neighbors: vec![],
+    };
+    let mut removed_per_wave = vec![];
+
+    while !wave.is_empty() {
+        let mut next = VecDeque::new();
+        let mut removed = 0;
+
+        match semantics {
+            Semantics::Generation => {
+                while let Some(point) = wave.pop_front() {
+                    erosion.remove(point, |neighbor| next.push_back(neighbor));
+                    removed += 1;
+                }
+            }
+            Semantics::Cascade => {
+                // Ordered by row and then column, smallest first
+                let reading_order = |point: Point| Reverse((point.y, point.x));
+                let mut sweep = wave.drain(..).map(reading_order).collect::<BinaryHeap<_>>();
+                while let Some(Reverse((y, x))) = sweep.pop() {
+                    erosion.remove(Point::new(x, y), |neighbor| {
+                        if (neighbor.y, neighbor.x) < (y, x) {
+                            next.push_back(neighbor);
+                        } else {
+                            sweep.push(reading_order(neighbor));
+                        }
+                    });
+                    removed += 1;
+                }
+            }
+        }
+
+        removed_per_wave.push(removed);
+        erosion.wave += 1;
+        wave = next;
+    }
+
+    removed_per_wave
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Solution;
+    use aoc_core::{Edges, Neighborhood};
+
+    /// Removes rolls the obvious way: sweep the whole grid for every wave, until a wave removes nothing.
+    fn sweep(grid: &Grid<Spot>, rule: &Rule, semantics: Semantics) -> Waves {
+        let reach = rule.reach();
+        let mut grid = grid.clone();
+        let mut waves = Waves::new(&grid);
+        loop {
+            let snapshot = grid.clone();
+            let mut removed = 0;
+            for point in grid.points() {
+                let before = match semantics {
+                    Semantics::Generation => &snapshot,
+                    Semantics::Cascade => &grid,
+                };
+                if grid[point] == Spot::Roll && reach.is_accessible(before, point) {
+                    grid[point] = Spot::Empty;
+                    waves.fates[point] = Fate::Removed(waves.removed.len());
+                    removed += 1;
+                }
+            }
+            if removed == 0 {
+                return waves;
+            }
+            waves.removed.push(removed);
+        }
+    }
+
+    #[test]
+    fn test_erode_matches_sweeping() {
+        let mut rng = aoc_core::Rng::new(4);
+        let mut next = |limit: u64| rng.below(limit) as usize;
+
+        for _ in 0..200 {
+            let density = next(10) + 1;
+            let grid = Grid::from_fn(next(30) + 1, next(30) + 1, |_| {
+                if next(10) < density {
+                    Spot::Roll
+                } else {
+                    Spot::Empty
+                }
+            });
+
+            let rule = if next(2) == 0 {
+                Rule::default()
+            } else {
+                Rule {
+                    neighborhood: [Neighborhood::Orthogonal, Neighborhood::All][next(2)],
+                    // Now and then too wide for a byte to hold the counts
+                    radius: if next(8) == 0 { 8 } else { next(4) },
+                    threshold: next(12),
+                    count_self: next(2) == 0,
+                    edges: [Edges::Bounded, Edges::Wrapping][next(2)],
+                }
+            };
+
+            let generation = waves(&grid, &rule, Semantics::Generation);
+            let cascade = waves(&grid, &rule, Semantics::Cascade);
+            assert_eq!(
+                generation,
+                sweep(&grid, &rule, Semantics::Generation),
+                "{:?}",
+                rule
+            );
+            assert_eq!(
+                cascade,
+                sweep(&grid, &rule, Semantics::Cascade),
+                "{:?}",
+                rule
+            );
+            assert_eq!(generation.total(), cascade.total());
+            assert_eq!(
+                erode(&grid, &rule, Semantics::Generation, None),
+                generation.removed
+            );
+        }
+    }
+
+    #[test]
+    fn test_semantics_differ() {
+        // Removing the top right roll frees up the one below it, and that one the roll below and to its left, but
+        // only a cascade gets to them in the same wave
+        let grid = crate::Day04::parse("@@@\n@@@\n@@.").unwrap().values;
+
+        assert_eq!(
+            erode(&grid, &Rule::default(), Semantics::Generation, None),
+            vec![3, 4, 1]
+        );
+        assert_eq!(
+            erode(&grid, &Rule::default(), Semantics::Cascade, None),
+            vec![5, 3]
+        );
+    }
+}
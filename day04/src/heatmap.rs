@@ -0,0 +1,120 @@
+use crate::{Fate, Waves};
+use std::fmt::Write;
+
+const EMPTY: [u8; 3] = [0, 0, 0];
+const LEFT: [u8; 3] = [200, 200, 200];
+/// Rolls removed in the first wave are this colour, fading to `LATEST` for the last wave
+const EARLIEST: [u8; 3] = [255, 230, 0];
+const LATEST: [u8; 3] = [130, 0, 90];
+
+impl Waves {
+    fn color(&self, fate: Fate) -> [u8; 3] {
+        match fate {
+            Fate::Empty => EMPTY,
+            Fate::Left => LEFT,
+            Fate::Removed(wave) => {
+                let t = wave as f64 / self.removed.len().saturating_sub(1).max(1) as f64;
+                [0, 1, 2].map(|i| {
+                    (EARLIEST[i] as f64 + (LATEST[i] as f64 - EARLIEST[i] as f64) * t).round() as u8
+                })
+            }
+        }
+    }
+
+    /// Three bytes per spot, row by row.
+    fn pixels(&self) -> Vec<u8> {
+        self.fates
+            .iter()
+            .flat_map(|(_, fate)| self.color(*fate))
+            .collect()
+    }
+
+    /// A line for every roll with the wave that removed it, counting from 1, or nothing if it never was.
+    pub fn to_csv(&self) -> String {
+        let mut csv = "x,y,wave\n".to_string();
+        for (point, fate) in self.fates.iter() {
+            match fate {
+                Fate::Empty => {}
+                Fate::Removed(wave) => {
+                    writeln!(csv, "{},{},{}", point.x, point.y, wave + 1).unwrap()
+                }
+                Fate::Left => writeln!(csv, "{},{},", point.x, point.y).unwrap(),
+            }
+        }
+        csv
+    }
+
+    /// A binary PPM with a pixel per spot, coloured by the wave that removed it.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm =
+            format!("P6\n{} {}\n255\n", self.fates.width(), self.fates.height()).into_bytes();
+        ppm.extend(self.pixels());
+        ppm
+    }
+
+    /// The same picture as [`Waves::to_ppm`] as a PNG.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut png = vec![];
+        let mut encoder = png::Encoder::new(
+            &mut png,
+            self.fates.width() as u32,
+            self.fates.height() as u32,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels()))
+            .expect("the parser rejects empty grids, so they always make a valid PNG");
+        png
+    }
+
+    /// The grid once the first `shown` waves are done, for a terminal. The rolls the last of those waves removed are
+    /// drawn in its colour and the ones removed before are faded out.
+    pub fn frame(&self, shown: usize) -> String {
+        let mut frame = String::new();
+        for y in 0..self.fates.height() {
+            for fate in self.fates.row(y) {
+                match fate {
+                    Fate::Empty => frame.push('.'),
+                    Fate::Removed(wave) if wave + 1 < shown => frame += "\x1b[2mx\x1b[0m",
+                    Fate::Removed(wave) if wave + 1 == shown => {
+                        let [r, g, b] = self.color(*fate);
+                        write!(frame, "\x1b[1;38;2;{};{};{}m@\x1b[0m", r, g, b).unwrap();
+                    }
+                    Fate::Removed(_) | Fate::Left => frame.push('@'),
+                }
+            }
+            frame.push('\n');
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Day04, Rule, Semantics};
+    use aoc_core::Solution;
+
+    #[test]
+    fn test_exports() {
+        let input = Day04::parse("@@@@@\n@@@@@\n@@@@@").unwrap();
+        let waves = crate::removal_waves(&input, &Rule::default(), Semantics::Generation);
+
+        // The corners go first, then the middles of the short sides, and the roll in the middle is last
+        assert_eq!(waves.removed, vec![4, 2, 4, 4, 1]);
+        assert!(
+            waves
+                .to_csv()
+                .starts_with("x,y,wave\n0,0,1\n1,0,3\n2,0,4\n")
+        );
+
+        let ppm = waves.to_ppm();
+        assert!(ppm.starts_with(b"P6\n5 3\n255\n"));
+        assert_eq!(ppm.len(), "P6\n5 3\n255\n".len() + 5 * 3 * 3);
+        assert!(waves.to_png().starts_with(b"\x89PNG"));
+
+        assert_eq!(waves.frame(0), "@@@@@\n@@@@@\n@@@@@\n");
+        assert!(waves.frame(waves.removed.len()).starts_with("\x1b[2mx"));
+    }
+}
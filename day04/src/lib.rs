@@ -0,0 +1,185 @@
+use aoc_core::{Grid, ParseError, Solution};
+
+mod erosion;
+mod heatmap;
+mod rule;
+
+pub use erosion::{Fate, Semantics, Waves};
+pub use rule::Rule;
+
+// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// struct Position {
+//     x: i64,
+//     y: i64,
+// }
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+enum Spot {
+    Roll,
+    Empty,
+}
+
+#[derive(Debug, Clone, Hash)]
+pub struct Input {
+    values: Grid<Spot>,
+}
+
+/// How many rolls are accessible by `rule` before any are removed.
+pub fn accessible(input: &Input, rule: &Rule) -> usize {
+    let reach = rule.reach();
+    input
+        .values
+        .iter()
+        .filter(|(point, spot)| **spot == Spot::Roll && reach.is_accessible(&input.values, *point))
+        .count()
+}
+
+/// How many rolls each wave removes with `rule` and `semantics`, until a wave removes nothing, and the wave that
+/// removed each roll.
+pub fn removal_waves(input: &Input, rule: &Rule, semantics: Semantics) -> Waves {
+    erosion::waves(&input.values, rule, semantics)
+}
+
+/// Just how many rolls each wave removes, which saves keeping the wave of every roll on big grids.
+pub fn removal_counts(input: &Input, rule: &Rule, semantics: Semantics) -> Vec<usize> {
+    erosion::erode(&input.values, rule, semantics, None)
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Input;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn part1(input: &Input) -> i64 {
+        accessible(input, &Rule::default()) as i64
+    }
+
+    fn part2(input: &Input) -> i64 {
+        removal_counts(input, &Rule::default(), Semantics::Generation)
+            .iter()
+            .sum::<usize>() as i64
+    }
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        Ok(Input {
+            values: Grid::parse(
+                input,
+                |c| match c {
+                    '.' => Some(Spot::Empty),
+                    '@' => Some(Spot::Roll),
+                    _ => None,
+                },
+                "'@' or '.'",
+            )?,
+        })
+
+        /*
+         * Alternative implementations:
+         */
+
+        // Two sections separated by a newline
+        // Input {
+        //     first: lines
+        //         .iter()
+        //         .take_while(|line| !line.is_empty())
+        //         .map(|line| line.split_once('|').unwrap())
+        //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
+        //         .collect_vec(),
+        //     second: lines
+        //         .iter()
+        //         .skip_while(|line| !line.is_empty())
+        //         .filter(|line| !line.is_empty())
+        //         .map(|line| {
+        //             line.split(',')
+        //                 .map(|page| page.parse::<i64>().unwrap())
+        //                 .collect_vec()
+        //         })
+        //         .collect_vec(),
+        // }
+
+        // Creates a HashMap<char, Vec<Position>>
+        // let map_limits = Position {
+        //     x: lines[0].len() as i64,
+        //     y: lines.len() as i64,
+        // };
+
+        // Input {
+        //     antennas: lines
+        //         .into_iter()
+        //         .enumerate()
+        //         .flat_map(|(y, line)| {
+        //             line.chars()
+        //                 .enumerate()
+        //                 .filter(|(_, c)| *c != '.')
+        //                 .map(|(x, c)| {
+        //                     (
+        //                         c,
+        //                         Position {
+        //                             x: x as i64,
+        //                             y: y as i64,
+        //                         },
+        //                     )
+        //                 })
+        //                 .collect_vec()
+        //         })
+        //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
+        //         .chunk_by(|(c, _)| *c)
+        //         .into_iter()
+        //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
+        //         .collect(),
+        //     map_limits,
+        // }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+            Day04::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result1 = Day04::part1(&input);
+
+        assert_eq!(result1, 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+            Day04::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result2 = Day04::part2(&input);
+
+        assert_eq!(result2, 43);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Day04::parse(input).unwrap_err();
+
+        let e = error("@.@\n@x@\n");
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 2, "'@' or '.'")
+        );
+        let e = error("@.@\n@.\n");
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 1, "a row 3 wide")
+        );
+        let e = error("@.@ \n");
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 4, " "));
+        let e = error("\n");
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (1, 1, "'@' or '.'")
+        );
+        assert_eq!(
+            format!("{:?}", Day04::parse("@.@\r\n.@.\r\n").unwrap()),
+            format!("{:?}", Day04::parse("@.@\n.@.\n").unwrap())
+        );
+    }
+}
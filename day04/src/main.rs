@@ -1,238 +1,139 @@
-use clap::Parser;
-use itertools::Itertools;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    #[arg(long, default_value = "")]
-    data_file: String,
-    #[arg(long)]
-    debug: bool,
-}
-
-// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-// struct Position {
-//     x: i64,
-//     y: i64,
-// }
-
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-enum Spot {
-    Roll,
-    Empty,
+use aoc_core::{Edges, Neighborhood};
+use clap::{Parser, ValueEnum};
+use day04::{Day04, Rule, Semantics};
+use std::{fs, path::Path, process::ExitCode, thread, time::Duration};
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Shape {
+    /// Cells within the radius in steps up, down, left and right
+    VonNeumann,
+    /// The square of cells within the radius
+    Moore,
 }
 
-#[derive(Debug, Clone, Hash)]
-struct Input {
-    values: Vec<Vec<Spot>>,
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Topology {
+    /// Nothing is past the edges
+    Bounded,
+    /// Edges wrap round to the other side
+    Toroidal,
 }
 
-fn main() {
-    let args = Args::parse();
-    let data_file = if args.data_file.is_empty() {
-        format!("{}/src/data.txt", env!("CARGO_MANIFEST_DIR"))
-    } else {
-        args.data_file
-    };
-
-    let input = parse(&data_file);
-
-    let result1 = part1(&input);
-    println!("Part1: {}", result1);
-
-    println!("Part 2: {}", part2(&input))
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(flatten)]
+    common: aoc_core::Args,
+    /// Print how many rolls each removal wave takes with these semantics, instead of the answers. Also used for
+    /// --heatmap and --animate, which otherwise use generation semantics.
+    #[arg(long, value_enum)]
+    waves: Option<Semantics>,
+    /// Which cells around a roll are looked at
+    #[arg(long, value_enum, default_value_t = Shape::Moore)]
+    neighborhood: Shape,
+    /// How far out the neighbourhood goes
+    #[arg(long, default_value_t = 1)]
+    radius: usize,
+    /// A roll can be reached when fewer than this many rolls are counted around it
+    #[arg(long, default_value_t = 4)]
+    threshold: usize,
+    /// Count the roll itself towards the threshold
+    #[arg(long)]
+    count_self: bool,
+    /// What is past the edges of the grid
+    #[arg(long, value_enum, default_value_t = Topology::Bounded)]
+    edges: Topology,
+    /// Save the wave that removed each roll as a heatmap, a .ppm, .png or .csv file
+    #[arg(long)]
+    heatmap: Option<String>,
+    /// Replay the removal waves in the terminal
+    #[arg(long)]
+    animate: bool,
+    /// Waves per second when animating
+    #[arg(long, default_value_t = 4, requires = "animate", value_parser = clap::value_parser!(u32).range(1..))]
+    fps: u32,
 }
 
-fn part1(input: &Input) -> i64 {
-    let height = input.values.len() as i32;
-    let width = input.values[0].len() as i32;
-
-    let mut count = 0;
-    for y in 0..height {
-        for x in 0..width {
-            if input.values[y as usize][x as usize] == Spot::Empty {
-                continue;
-            }
-            let mut surrounding = 0;
-            for dy in -1..=1 {
-                let effective_y = y as i32 + dy;
-                if effective_y < 0 || effective_y >= height {
-                    continue;
-                }
-
-                for dx in -1..=1 {
-                    let effective_x = x as i32 + dx;
-
-                    if effective_x >= 0 && effective_x < width {
-                        if input.values[effective_y as usize][effective_x as usize] == Spot::Roll {
-                            surrounding += 1;
-                        }
-                    }
-                }
-            }
-
-            // The brief is less than 4, but we count ourselves so make it 5
-            if surrounding < 5 {
-                count += 1;
-            }
+impl Cli {
+    fn rule(&self) -> Rule {
+        Rule {
+            neighborhood: match self.neighborhood {
+                Shape::VonNeumann => Neighborhood::Orthogonal,
+                Shape::Moore => Neighborhood::All,
+            },
+            radius: self.radius,
+            threshold: self.threshold,
+            count_self: self.count_self,
+            edges: match self.edges {
+                Topology::Bounded => Edges::Bounded,
+                Topology::Toroidal => Edges::Wrapping,
+            },
         }
     }
-
-    count
 }
 
-fn remove_available(grid: &mut Vec<Vec<Spot>>) -> i64 {
-    let height = grid.len() as i32;
-    let width = grid[0].len() as i32;
-
-    let mut count = 0;
-    for y in 0..height {
-        for x in 0..width {
-            if grid[y as usize][x as usize] == Spot::Empty {
-                continue;
-            }
-            let mut surrounding = 0;
-            for dy in -1..=1 {
-                let effective_y = y as i32 + dy;
-                if effective_y < 0 || effective_y >= height {
-                    continue;
-                }
-
-                for dx in -1..=1 {
-                    let effective_x = x as i32 + dx;
-
-                    if effective_x >= 0 && effective_x < width {
-                        if grid[effective_y as usize][effective_x as usize] == Spot::Roll {
-                            surrounding += 1;
-                        }
-                    }
-                }
-            }
-
-            // The brief is less than 4, but we count ourselves so make it 5
-            if surrounding < 5 {
-                grid[y as usize][x as usize] = Spot::Empty;
-                count += 1;
-            }
-        }
+fn main() -> ExitCode {
+    let args = Cli::parse();
+    let heatmap_format = args.heatmap.as_ref().map(|file| {
+        Path::new(file)
+            .extension()
+            .and_then(|extension| extension.to_str())
+    });
+    if let Some(format) = heatmap_format
+        && !matches!(format, Some("ppm" | "png" | "csv"))
+    {
+        eprintln!("error: --heatmap needs a .ppm, .png or .csv file");
+        return ExitCode::FAILURE;
     }
 
-    count
-}
-
-fn part2(input: &Input) -> i64 {
-    let mut count_sum = 0;
-    let mut grid = input.values.clone();
-
-    loop {
-        let removed = remove_available(&mut grid);
-        if removed == 0 {
-            break;
+    let Some(input) = aoc_core::load::<Day04>(&args.common, env!("CARGO_MANIFEST_DIR")) else {
+        return ExitCode::FAILURE;
+    };
+    let semantics = args.waves.unwrap_or_default();
+    // The wave of every roll is only kept when it is going to be drawn, as it takes a lot of room on big grids
+    let waves = (args.heatmap.is_some() || args.animate)
+        .then(|| day04::removal_waves(&input, &args.rule(), semantics));
+    let removed = match &waves {
+        Some(waves) => waves.removed.clone(),
+        None => day04::removal_counts(&input, &args.rule(), semantics),
+    };
+    let total = removed.iter().sum::<usize>();
+
+    if let Some(file) = &args.heatmap
+        && let Some(waves) = &waves
+    {
+        let contents = match heatmap_format.flatten() {
+            Some("ppm") => waves.to_ppm(),
+            Some("png") => waves.to_png(),
+            _ => waves.to_csv().into_bytes(),
+        };
+        if let Err(e) = fs::write(file, contents) {
+            eprintln!("error: failed to write {}: {}", file, e);
+            return ExitCode::FAILURE;
         }
-        count_sum += removed;
     }
 
-    count_sum
-}
-
-fn parse(file: &str) -> Input {
-    let file = File::open(file).expect("Failed to open file");
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader
-        .lines()
-        .map(|line| line.expect("Failed to read line"))
-        .collect();
-
-    Input {
-        values: lines
-            .iter()
-            .map(|line| {
-                line.chars()
-                    .map(|c| if c == '.' { Spot::Empty } else { Spot::Roll })
-                    .collect()
-            })
-            .collect_vec(),
+    if args.animate
+        && let Some(waves) = &waves
+    {
+        for shown in 0..=waves.removed.len() {
+            // Clear the screen and go back to the top left before drawing the next frame
+            print!("\x1b[2J\x1b[H{}", waves.frame(shown));
+            println!("Wave {} of {}", shown, waves.removed.len());
+            thread::sleep(Duration::from_secs(1) / args.fps);
+        }
     }
 
-    /*
-     * Alternative implementations:
-     */
-
-    // Two sections separated by a newline
-    // Input {
-    //     first: lines
-    //         .iter()
-    //         .take_while(|line| !line.is_empty())
-    //         .map(|line| line.split_once('|').unwrap())
-    //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
-    //         .collect_vec(),
-    //     second: lines
-    //         .iter()
-    //         .skip_while(|line| !line.is_empty())
-    //         .filter(|line| !line.is_empty())
-    //         .map(|line| {
-    //             line.split(',')
-    //                 .map(|page| page.parse::<i64>().unwrap())
-    //                 .collect_vec()
-    //         })
-    //         .collect_vec(),
-    // }
-
-    // Creates a HashMap<char, Vec<Position>>
-    // let map_limits = Position {
-    //     x: lines[0].len() as i64,
-    //     y: lines.len() as i64,
-    // };
-
-    // Input {
-    //     antennas: lines
-    //         .into_iter()
-    //         .enumerate()
-    //         .flat_map(|(y, line)| {
-    //             line.chars()
-    //                 .enumerate()
-    //                 .filter(|(_, c)| *c != '.')
-    //                 .map(|(x, c)| {
-    //                     (
-    //                         c,
-    //                         Position {
-    //                             x: x as i64,
-    //                             y: y as i64,
-    //                         },
-    //                     )
-    //                 })
-    //                 .collect_vec()
-    //         })
-    //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
-    //         .chunk_by(|(c, _)| *c)
-    //         .into_iter()
-    //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
-    //         .collect(),
-    //     map_limits,
-    // }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        let input = parse(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result1 = part1(&input);
-
-        assert_eq!(result1, 13);
+    match args.waves {
+        Some(_) => {
+            for (index, count) in removed.iter().enumerate() {
+                println!("Wave {}: {} rolls", index + 1, count);
+            }
+            println!("Total: {} rolls in {} waves", total, removed.len());
+        }
+        // The default rule is the puzzle's, so this gives the usual answers
+        None => aoc_core::print_answers(day04::accessible(&input, &args.rule()), total),
     }
 
-    #[test]
-    fn test_part2() {
-        let input = parse(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result2 = part2(&input);
-
-        assert_eq!(result2, 43);
-    }
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,113 @@
+use crate::Spot;
+use aoc_core::{Edges, Grid, Neighborhood, Point};
+
+/// When a forklift can get to a roll: when fewer than `threshold` rolls are counted around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// `Orthogonal` takes the cells within `radius` steps up, down, left and right (von Neumann), `All` the square
+    /// `radius` cells out on every side (Moore)
+    pub neighborhood: Neighborhood,
+    pub radius: usize,
+    pub threshold: usize,
+    /// Count the roll itself along with the rolls around it
+    pub count_self: bool,
+    /// On a torus smaller than the neighbourhood a cell is counted once for every offset that lands on it, which can
+    /// include the roll itself
+    pub edges: Edges,
+}
+
+/// The puzzle's rule: fewer than four rolls in the eight cells around it.
+impl Default for Rule {
+    fn default() -> Self {
+        Self {
+            neighborhood: Neighborhood::All,
+            radius: 1,
+            threshold: 4,
+            count_self: false,
+            edges: Edges::Bounded,
+        }
+    }
+}
+
+impl Rule {
+    /// The rule with its neighbourhood worked out, ready to apply to a grid.
+    pub(crate) fn reach(&self) -> Reach {
+        let radius = self.radius as isize;
+        let offsets = (-radius..=radius)
+            .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+            .filter(|(dx, dy)| (*dx, *dy) != (0, 0))
+            .filter(|(dx, dy)| match self.neighborhood {
+                Neighborhood::Orthogonal => dx.abs() + dy.abs() <= radius,
+                Neighborhood::All => true,
+            })
+            .collect();
+
+        Reach {
+            offsets,
+            edges: self.edges,
+            // A roll that counts itself has room for one fewer neighbour
+            limit: self.threshold.saturating_sub(self.count_self as usize),
+        }
+    }
+}
+
+pub(crate) struct Reach {
+    offsets: Vec<(isize, isize)>,
+    edges: Edges,
+    /// Rolls with fewer than this many rolls around them are accessible
+    pub limit: usize,
+}
+
+impl Reach {
+    /// The cells around `point`, once for each offset that reaches them. Every cell reaches `point` as many times as
+    /// `point` reaches it, as the offsets are symmetric.
+    pub fn around<'a, T>(
+        &'a self,
+        grid: &'a Grid<T>,
+        point: Point,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.offsets
+            .iter()
+            .filter_map(move |(dx, dy)| grid.offset(point, *dx, *dy, self.edges))
+    }
+
+    /// The most rolls that can be counted around a spot.
+    pub fn most(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn rolls_around(&self, grid: &Grid<Spot>, point: Point) -> usize {
+        self.around(grid, point)
+            .filter(|neighbor| grid[*neighbor] == Spot::Roll)
+            .count()
+    }
+
+    pub fn is_accessible(&self, grid: &Grid<Spot>, point: Point) -> bool {
+        self.rolls_around(grid, point) < self.limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighborhood_sizes() {
+        let size = |neighborhood, radius| {
+            Rule {
+                neighborhood,
+                radius,
+                ..Rule::default()
+            }
+            .reach()
+            .offsets
+            .len()
+        };
+
+        assert_eq!(size(Neighborhood::All, 1), 8);
+        assert_eq!(size(Neighborhood::All, 2), 24);
+        assert_eq!(size(Neighborhood::Orthogonal, 1), 4);
+        assert_eq!(size(Neighborhood::Orthogonal, 2), 12);
+        assert_eq!(size(Neighborhood::Orthogonal, 0), 0);
+    }
+}
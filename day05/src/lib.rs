@@ -0,0 +1,202 @@
+use aoc_core::{IntervalSet, ParseError, Solution};
+use itertools::Itertools;
+use serde::Serialize;
+
+mod report;
+
+pub use report::{Explanation, Gap, Report};
+
+// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// struct Position {
+//     x: i64,
+//     y: i64,
+// }
+
+/// A range of fresh IDs as written in the input.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FreshRange {
+    pub start: i64,
+    pub end: i64,
+    pub line: usize,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ingredient {
+    pub id: i64,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Hash)]
+pub struct Input {
+    fresh_ranges: Vec<FreshRange>,
+    ingredients: Vec<Ingredient>,
+}
+
+impl Input {
+    /// Every fresh ingredient ID, with the ranges merged.
+    pub fn fresh(&self) -> IntervalSet<i64> {
+        self.fresh_ranges
+            .iter()
+            .map(|range| (range.start, range.end))
+            .collect()
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Input;
+    type Output1 = i64;
+    type Output2 = u128;
+
+    fn part1(input: &Input) -> i64 {
+        let fresh = input.fresh();
+
+        input
+            .ingredients
+            .iter()
+            .filter(|ingredient| fresh.contains(ingredient.id))
+            .count() as i64
+    }
+
+    fn part2(input: &Input) -> u128 {
+        input.fresh().covered_len()
+    }
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        let lines = aoc_core::lines(input).collect_vec();
+
+        Ok(Input {
+            fresh_ranges: lines
+                .iter()
+                .take_while(|line| !line.text.is_empty())
+                .map(|line| {
+                    let (start, end) = line.split_once(line.text, '-')?;
+                    Ok(FreshRange {
+                        start: line.parse::<i64>(start, "the start of a range")?,
+                        end: line.parse::<i64>(end, "the end of a range")?,
+                        line: line.number,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            ingredients: lines
+                .iter()
+                .skip_while(|line| !line.text.is_empty())
+                .filter(|line| !line.text.is_empty())
+                .map(|line| {
+                    Ok(Ingredient {
+                        id: line.parse::<i64>(line.text, "an ingredient ID")?,
+                        line: line.number,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+
+        /*
+         * Alternative implementations:
+         */
+
+        // Two sections separated by a newline
+        // Input {
+        //     first: lines
+        //         .iter()
+        //         .take_while(|line| !line.is_empty())
+        //         .map(|line| line.split_once('|').unwrap())
+        //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
+        //         .collect_vec(),
+        //     second: lines
+        //         .iter()
+        //         .skip_while(|line| !line.is_empty())
+        //         .filter(|line| !line.is_empty())
+        //         .map(|line| {
+        //             line.split(',')
+        //                 .map(|page| page.parse::<i64>().unwrap())
+        //                 .collect_vec()
+        //         })
+        //         .collect_vec(),
+        // }
+
+        // Creates a HashMap<char, Vec<Position>>
+        // let map_limits = Position {
+        //     x: lines[0].len() as i64,
+        //     y: lines.len() as i64,
+        // };
+
+        // Input {
+        //     antennas: lines
+        //         .into_iter()
+        //         .enumerate()
+        //         .flat_map(|(y, line)| {
+        //             line.chars()
+        //                 .enumerate()
+        //                 .filter(|(_, c)| *c != '.')
+        //                 .map(|(x, c)| {
+        //                     (
+        //                         c,
+        //                         Position {
+        //                             x: x as i64,
+        //                             y: y as i64,
+        //                         },
+        //                     )
+        //                 })
+        //                 .collect_vec()
+        //         })
+        //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
+        //         .chunk_by(|(c, _)| *c)
+        //         .into_iter()
+        //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
+        //         .collect(),
+        //     map_limits,
+        // }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+            Day05::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result1 = Day05::part1(&input);
+
+        assert_eq!(result1, 3);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+            Day05::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result2 = Day05::part2(&input);
+
+        assert_eq!(result2, 14);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Day05::parse(input).unwrap_err();
+
+        let e = error("3-5\n10x14\n\n1\n");
+        assert_eq!(
+            (e.line, e.column, e.found.as_str(), e.expected.as_str()),
+            (2, 1, "10x14", "'-'")
+        );
+        let e = error("3-5\n\n5 \n");
+        assert_eq!(
+            (e.line, e.column, e.found.as_str(), e.expected.as_str()),
+            (3, 1, "5 ", "an ingredient ID")
+        );
+        assert_eq!(
+            format!("{:?}", Day05::parse("3-5\r\n\r\n5\r\n").unwrap()),
+            format!("{:?}", Day05::parse("3-5\n\n5\n").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_coverage_past_i64() {
+        let input = Day05::parse(&format!("0-{}\n\n1\n", i64::MAX)).unwrap();
+
+        assert_eq!(Day05::part2(&input), 1 << 63);
+    }
+}
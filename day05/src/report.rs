@@ -0,0 +1,219 @@
+use crate::{FreshRange, Ingredient, Input};
+use aoc_core::Integer;
+use itertools::Itertools;
+use serde::Serialize;
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display},
+};
+
+/// Why an ingredient is fresh or spoiled.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub ingredient: Ingredient,
+    pub fresh: bool,
+    /// The ranges the ID is in, in input order
+    pub containing: Vec<FreshRange>,
+    /// For a spoiled ingredient, the range that ends closest below its ID
+    pub below: Option<FreshRange>,
+    /// For a spoiled ingredient, the range that starts closest above its ID
+    pub above: Option<FreshRange>,
+}
+
+/// IDs between the first and last fresh ones that no range covers.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    pub start: i64,
+    pub end: i64,
+    pub length: u128,
+}
+
+/// An explanation for every ingredient, in input order, and the gaps between the fresh ranges.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub ingredients: Vec<Explanation>,
+    pub gaps: Vec<Gap>,
+}
+
+impl Report {
+    /// Sweeps the ingredients in order of ID, keeping the ranges that have started in a set ordered by where they
+    /// end. Once the ones that ended before an ID are dropped, the rest all contain it, so the work is the sorting
+    /// plus the size of the report.
+    pub fn new(input: &Input) -> Self {
+        // On ties the range from the earlier line wins, for both of these
+        let by_start = input
+            .fresh_ranges
+            .iter()
+            .copied()
+            .sorted_by_key(|range| (range.start, range.line))
+            .collect_vec();
+        let by_end = input
+            .fresh_ranges
+            .iter()
+            .copied()
+            .sorted_by_key(|range| (range.end, std::cmp::Reverse(range.line)))
+            .collect_vec();
+
+        let mut explanations = vec![None; input.ingredients.len()];
+        let mut started = 0;
+        let mut active = BTreeSet::new();
+        for index in
+            (0..input.ingredients.len()).sorted_by_key(|index| input.ingredients[*index].id)
+        {
+            let ingredient = input.ingredients[index];
+            while started < by_start.len() && by_start[started].start <= ingredient.id {
+                active.insert((by_start[started].end, started));
+                started += 1;
+            }
+            while active.first().is_some_and(|(end, _)| *end < ingredient.id) {
+                active.pop_first();
+            }
+
+            let containing = active
+                .iter()
+                .map(|(_, position)| by_start[*position])
+                .sorted_by_key(|range| range.line)
+                .collect_vec();
+            let fresh = !containing.is_empty();
+            let (below, above) = if fresh {
+                (None, None)
+            } else {
+                let below = by_end.partition_point(|range| range.end < ingredient.id);
+                (
+                    below.checked_sub(1).map(|below| by_end[below]),
+                    by_start.get(started).copied(),
+                )
+            };
+
+            explanations[index] = Some(Explanation {
+                ingredient,
+                fresh,
+                containing,
+                below,
+                above,
+            });
+        }
+
+        let fresh = input.fresh();
+        let gaps = match (fresh.ranges().first(), fresh.ranges().last()) {
+            (Some((start, _)), Some((_, end))) => fresh
+                .complement(*start, *end)
+                .ranges()
+                .iter()
+                .map(|(start, end)| Gap {
+                    start: *start,
+                    end: *end,
+                    length: i64::count(*start, *end),
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        Self {
+            ingredients: explanations.into_iter().flatten().collect(),
+            gaps,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("reports always serialize")
+    }
+}
+
+impl Display for FreshRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{} (line {})", self.start, self.end, self.line)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for explanation in self.ingredients.iter() {
+            let ingredient = explanation.ingredient;
+            write!(
+                f,
+                "Ingredient {} (line {}): ",
+                ingredient.id, ingredient.line
+            )?;
+            if explanation.fresh {
+                writeln!(f, "fresh, in {}", explanation.containing.iter().join(", "))?;
+                continue;
+            }
+            match (explanation.below, explanation.above) {
+                (Some(below), Some(above)) => {
+                    writeln!(f, "spoiled, between {} and {}", below, above)?
+                }
+                (Some(below), None) => writeln!(f, "spoiled, after {}", below)?,
+                (None, Some(above)) => writeln!(f, "spoiled, before {}", above)?,
+                (None, None) => writeln!(f, "spoiled, there are no fresh ranges")?,
+            }
+        }
+
+        writeln!(f, "{} gaps between the fresh ranges", self.gaps.len())?;
+        for gap in self.gaps.iter() {
+            writeln!(f, "  {}-{} ({} IDs)", gap.start, gap.end, gap.length)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day05;
+    use aoc_core::Solution;
+
+    #[test]
+    fn test_report() {
+        let input = Day05::parse("3-5\n10-14\n16-20\n12-18\n30-30\n\n1\n5\n8\n11\n17\n32").unwrap();
+        let report = Report::new(&input);
+        let range = |start, end, line| FreshRange { start, end, line };
+
+        let ids = report
+            .ingredients
+            .iter()
+            .map(|explanation| (explanation.ingredient.id, explanation.fresh))
+            .collect_vec();
+        assert_eq!(
+            ids,
+            vec![
+                (1, false),
+                (5, true),
+                (8, false),
+                (11, true),
+                (17, true),
+                (32, false)
+            ]
+        );
+        assert_eq!(report.ingredients[0].above, Some(range(3, 5, 1)));
+        assert_eq!(report.ingredients[0].below, None);
+        assert_eq!(report.ingredients[2].below, Some(range(3, 5, 1)));
+        assert_eq!(report.ingredients[2].above, Some(range(10, 14, 2)));
+        assert_eq!(
+            report.ingredients[4].containing,
+            vec![range(16, 20, 3), range(12, 18, 4)]
+        );
+        assert_eq!(report.ingredients[5].below, Some(range(30, 30, 5)));
+        assert_eq!(
+            report.gaps,
+            vec![
+                Gap {
+                    start: 6,
+                    end: 9,
+                    length: 4
+                },
+                Gap {
+                    start: 21,
+                    end: 29,
+                    length: 9
+                }
+            ]
+        );
+        assert!(
+            report.to_string().contains(
+                "Ingredient 8 (line 9): spoiled, between 3-5 (line 1) and 10-14 (line 2)"
+            )
+        );
+        assert!(report.to_json().contains("\"length\": 9"));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{Operator, Problem, Worksheet};
+use aoc_core::Rng;
+
+/// Which side of its problem a number is pushed up against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+impl Worksheet {
+    /// Lays out problems the way the puzzle does. Each problem is as wide as its longest number, the operator goes
+    /// under its first column, and a blank column separates it from the next. A problem with fewer numbers than the
+    /// others leaves its bottom rows blank. `align` places the number in each row of each problem.
+    pub fn from_problems(
+        problems: &[(Vec<u64>, Operator)],
+        mut align: impl FnMut(usize, usize) -> Align,
+    ) -> Self {
+        let rows = problems
+            .iter()
+            .map(|(numbers, _)| numbers.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut column = 0;
+        let problems = problems
+            .iter()
+            .enumerate()
+            .map(|(index, (numbers, operator))| {
+                let width = numbers
+                    .iter()
+                    .map(|number| number.to_string().len())
+                    .max()
+                    .unwrap_or(1);
+                let cells = (0..rows)
+                    .map(|y| {
+                        let mut row = vec![None; width];
+                        if let Some(number) = numbers.get(y) {
+                            let digits = number.to_string();
+                            let start = match align(index, y) {
+                                Align::Left => 0,
+                                Align::Right => width - digits.len(),
+                            };
+                            for (x, digit) in digits.bytes().enumerate() {
+                                row[start + x] = Some(digit - b'0');
+                            }
+                        }
+                        row
+                    })
+                    .collect();
+
+                let problem = Problem {
+                    column,
+                    cells,
+                    operator: *operator,
+                    operator_column: 0,
+                };
+                column += width + 1;
+                problem
+            })
+            .collect();
+
+        Self {
+            problems,
+            width: column.saturating_sub(1),
+        }
+    }
+
+    /// A worksheet of `count` random problems, each with one to `max_numbers` numbers of one to `max_digits` digits,
+    /// pushed to either side at random. The same seed always gives the same worksheet. Numbers are u64, so any
+    /// `max_digits` past 20 is the same as 20.
+    pub fn random(seed: u64, count: usize, max_numbers: usize, max_digits: u32) -> Self {
+        let mut rng = Rng::new(seed);
+        let mut next = |limit: u64| rng.below(limit);
+
+        let mut problems = vec![];
+        for _ in 0..count {
+            let mut numbers = vec![];
+            for _ in 0..=next(max_numbers as u64) {
+                let digits = next(max_digits as u64) as u32 + 1;
+                numbers.push(next(10_u64.checked_pow(digits).unwrap_or(u64::MAX)));
+            }
+            let operator = Operator::ALL[next(Operator::ALL.len() as u64) as usize];
+            problems.push((numbers, operator));
+        }
+
+        Self::from_problems(&problems, |_, _| {
+            if next(2) == 0 {
+                Align::Left
+            } else {
+                Align::Right
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Columns, ReadingOrder, Rows};
+
+    #[test]
+    fn test_puzzle_layout() {
+        let problems = [
+            (vec![123, 45, 6], Operator::Multiply),
+            (vec![328, 64, 98], Operator::Add),
+            (vec![51, 387, 215], Operator::Multiply),
+            (vec![64, 23, 314], Operator::Add),
+        ];
+        let aligns = [Align::Right, Align::Left, Align::Right, Align::Left];
+        let worksheet = Worksheet::from_problems(&problems, |problem, _| aligns[problem]);
+
+        assert_eq!(
+            worksheet.to_string(),
+            "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n"
+        );
+    }
+
+    #[test]
+    fn test_long_numbers() {
+        let numbers = |max_digits| {
+            let worksheet = Worksheet::random(6, 20, 5, max_digits);
+            worksheet
+                .read(&Rows)
+                .flat_map(|(numbers, _)| numbers)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(numbers(15).iter().any(|number| number.len() > 10));
+        assert!(numbers(30).iter().all(|number| number.len() <= 20));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let orders: [&dyn ReadingOrder; 3] = [&Rows, &Columns::RightToLeft, &Columns::LeftToRight];
+
+        for seed in 0..300 {
+            let worksheet = Worksheet::random(seed, 1 + seed as usize % 7, 5, 4);
+            let rendered = worksheet.to_string();
+            let parsed = Worksheet::parse(&rendered).unwrap();
+
+            assert_eq!(parsed, worksheet, "{}", rendered);
+            assert_eq!(parsed.to_string(), rendered);
+            for order in orders {
+                assert!(parsed.read(order).eq(worksheet.read(order)), "{}", rendered);
+            }
+        }
+    }
+}
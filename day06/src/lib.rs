@@ -0,0 +1,169 @@
+use aoc_core::{Checked, ParseError, Solution};
+
+mod generate;
+mod operator;
+mod worksheet;
+
+pub use generate::Align;
+pub use operator::{EvalError, Failure, Fold, Number, Operator, grand_total};
+pub use worksheet::{Columns, Problem, ReadingOrder, Rows, Worksheet};
+
+// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// struct Position {
+//     x: i64,
+//     y: i64,
+// }
+
+#[derive(Debug, Clone, Hash)]
+pub struct Input {
+    worksheet: Worksheet,
+}
+
+impl Input {
+    pub fn worksheet(&self) -> &Worksheet {
+        &self.worksheet
+    }
+}
+
+/// The answers to every problem, reading the numbers with `order`, added up.
+pub fn total(input: &Input, order: &dyn ReadingOrder) -> Result<Number, EvalError> {
+    let problems = input
+        .worksheet
+        .read(order)
+        .enumerate()
+        .map(|(problem, (numbers, operator))| {
+            let numbers = numbers
+                .iter()
+                .map(|digits| Number::from_digits(digits))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(EvalError {
+                    problem,
+                    operator,
+                    failure: Failure::Overflow,
+                })?;
+            Ok((numbers, operator))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    grand_total(
+        problems
+            .iter()
+            .map(|(numbers, operator)| (numbers.as_slice(), *operator)),
+    )
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Input;
+    type Output1 = Result<Number, EvalError>;
+    type Output2 = Result<Number, EvalError>;
+
+    fn part1(input: &Input) -> Result<Number, EvalError> {
+        total(input, &Rows)
+    }
+
+    fn part2(input: &Input) -> Result<Number, EvalError> {
+        total(input, &Columns::RightToLeft)
+    }
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        Ok(Input {
+            worksheet: Worksheet::parse(input)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+            Day06::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result1 = Day06::part1(&input);
+
+        assert_eq!(result1, Ok(Number::from(4277556)));
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+            Day06::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result2 = Day06::part2(&input);
+
+        assert_eq!(result2, Ok(Number::from(3263827)));
+    }
+
+    #[test]
+    fn test_unknown_operator() {
+        let error = Day06::parse("1 2\n3 4\n+ x\n").unwrap_err().to_string();
+
+        assert!(error.contains("expected an operator"), "{}", error);
+        assert!(error.contains("3:3"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Day06::parse(input).unwrap_err();
+
+        let e = error("1 2\n3 4\n  *\n");
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (3, 1, "an operator under this problem")
+        );
+        // Lines are padded out with spaces, so trailing spaces are fine but other trailing whitespace isn't
+        let e = error("1 2\t\n3 4\n+ *\n");
+        assert_eq!(
+            (e.line, e.column, e.found.as_str(), e.expected.as_str()),
+            (1, 4, "\t", "a digit")
+        );
+        assert_eq!(
+            format!("{:?}", Day06::parse("1 2   \n3 4\n+ *  \n").unwrap()),
+            format!("{:?}", Day06::parse("1 2\n3 4\n+ *\n").unwrap())
+        );
+        assert_eq!(
+            format!("{:?}", Day06::parse("12 3\r\n 4 56\r\n*  +\r\n").unwrap()),
+            format!("{:?}", Day06::parse("12 3\n 4 56\n*  +\n").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_reading_orders() {
+        let input = Day06::parse("64 8\n 2 3\n-  ^").unwrap();
+
+        assert_eq!(
+            total(&input, &Rows),
+            Ok(Number::from(64 - 2 + 8_i32.pow(3)))
+        );
+        // Read by columns the second problem is the single number 83, which is its own answer
+        assert_eq!(
+            total(&input, &Columns::RightToLeft),
+            Ok(Number::from(42 - 6 + 83))
+        );
+        assert_eq!(
+            total(&input, &Columns::LeftToRight),
+            Ok(Number::from(6 - 42 + 83))
+        );
+    }
+
+    #[test]
+    fn test_number_overflow_names_problem() {
+        // 40 nines don't fit in 128 bits
+        let input = Day06::parse(&format!("1 {}\n2 1\n+ *", "9".repeat(40))).unwrap();
+        let result = total(&input, &Rows);
+
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(
+            result,
+            Err(EvalError {
+                problem: 1,
+                operator: Operator::Multiply,
+                failure: Failure::Overflow
+            })
+        );
+        #[cfg(feature = "bigint")]
+        assert_eq!(result.unwrap().to_string(), format!("1{}2", "0".repeat(39)));
+    }
+}
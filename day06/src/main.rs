@@ -1,181 +1,70 @@
-use clap::Parser;
-use itertools::Itertools;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use clap::{Parser, ValueEnum};
+use day06::{Columns, Day06, ReadingOrder, Rows, Worksheet};
+use std::process::ExitCode;
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Order {
+    Rows,
+    RightToLeft,
+    LeftToRight,
+}
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-    #[arg(long, default_value = "")]
-    data_file: String,
-    #[arg(long)]
-    debug: bool,
-}
-
-// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-// struct Position {
-//     x: i64,
-//     y: i64,
-// }
-
-#[derive(Debug, Clone, Hash)]
-struct Input {
-    values: Vec<Vec<i64>>,
-    operations: Vec<char>,
-}
-
-#[derive(Debug, Clone, Hash)]
-struct Input2 {
-    problems: Vec<(Vec<i64>, char)>,
-}
-
-fn main() {
-    let args = Args::parse();
-    let data_file = if args.data_file.is_empty() {
-        format!("{}/src/data.txt", env!("CARGO_MANIFEST_DIR"))
-    } else {
-        args.data_file
-    };
-
-    let input = parse(&data_file);
-    let input2 = parse2(&data_file);
-
-    let result1 = part1(&input);
-    println!("Part1: {}", result1);
-
-    println!("Part 2: {}", part2(&input2))
+struct Cli {
+    #[command(flatten)]
+    common: aoc_core::Args,
+    /// Read every problem this way and print the grand total, instead of the answers. Part 1 reads rows and part 2
+    /// reads columns right to left.
+    #[arg(long, value_enum)]
+    order: Option<Order>,
+    /// Print a random worksheet made from this seed instead of solving one
+    #[arg(long, conflicts_with_all = ["order", "render"])]
+    generate: Option<u64>,
+    /// How many problems the generated worksheet has
+    #[arg(long, default_value_t = 10, requires = "generate")]
+    problems: usize,
+    /// How many digits the generated numbers have at most, up to the 20 a u64 holds
+    #[arg(long, default_value_t = 4, requires = "generate", value_parser = clap::value_parser!(u32).range(1..=20))]
+    digits: u32,
+    /// Print the worksheet laid out again from what was parsed, instead of the answers
+    #[arg(long, conflicts_with = "order")]
+    render: bool,
 }
 
-fn part1(input: &Input) -> i64 {
-    let mut sum = 0;
-    for i in 0..input.operations.len() {
-        let numbers = input
-            .values
-            .iter()
-            .map(|numbers| numbers.get(i).unwrap())
-            .collect_vec();
-
-        let operation = input.operations.get(i).unwrap();
-
-        if *operation == '+' {
-            sum += numbers.iter().cloned().cloned().sum::<i64>();
-        } else if *operation == '*' {
-            sum += numbers
-                .iter()
-                .cloned()
-                .cloned()
-                .reduce(|a, b| a * b)
-                .unwrap();
-        }
+fn main() -> ExitCode {
+    let args = Cli::parse();
+    if let Some(seed) = args.generate {
+        print!("{}", Worksheet::random(seed, args.problems, 4, args.digits));
+        return ExitCode::SUCCESS;
     }
-
-    sum
-}
-
-fn part2(input: &Input2) -> i64 {
-    let mut sum = 0;
-    for problem in input.problems.iter() {
-        let numbers = &problem.0;
-
-        let operation = problem.1;
-
-        if operation == '+' {
-            sum += numbers.into_iter().cloned().sum::<i64>();
-        } else if operation == '*' {
-            sum += numbers.into_iter().cloned().reduce(|a, b| a * b).unwrap();
-        }
-    }
-
-    sum
-}
-
-fn parse(file: &str) -> Input {
-    let file = File::open(file).expect("Failed to open file");
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader
-        .lines()
-        .map(|line| line.expect("Failed to read line"))
-        .collect();
-
-    Input {
-        values: lines
-            .iter()
-            .take(lines.len() - 1)
-            .map(|line| {
-                line.split_ascii_whitespace()
-                    .map(|v| v.parse::<i64>().unwrap())
-                    .collect()
-            })
-            .collect_vec(),
-        operations: lines
-            .last()
-            .unwrap()
-            .split_ascii_whitespace()
-            .map(|v| v.chars().take(1).last().unwrap())
-            .collect_vec(),
+    let Some(input) = aoc_core::load::<Day06>(&args.common, env!("CARGO_MANIFEST_DIR")) else {
+        return ExitCode::FAILURE;
+    };
+    if args.render {
+        print!("{}", input.worksheet());
+        return ExitCode::SUCCESS;
     }
-}
-
-fn parse2(file: &str) -> Input2 {
-    let file = File::open(file).expect("Failed to open file");
-    let reader = BufReader::new(file);
-    let lines: Vec<Vec<char>> = reader
-        .lines()
-        .map(|line| line.expect("Failed to read line").chars().collect_vec())
-        .collect();
-
-    let number_rows = lines.len() - 1;
-
-    let mut problems = Vec::new();
-    // White space maters here, the last line is the easiest to find the end / start of the next set of numbers
-    let mut index = lines[0].len() - 1;
-    let mut current_section = Vec::new();
-    loop {
-        let mut current_column = 0;
-        for row in 0..number_rows {
-            if lines[row][index] != ' ' {
-                current_column = current_column * 10;
-                current_column += lines[row][index].to_string().parse::<i64>().unwrap();
-            }
-        }
-
-        if current_column > 0 {
-            current_section.push(current_column);
-        }
 
-        if lines[number_rows][index] != ' ' {
-            problems.push((current_section, lines[number_rows][index]));
-            current_section = Vec::new();
-        }
-
-        if index == 0 {
-            break;
+    let orders: Vec<&dyn ReadingOrder> = match args.order {
+        Some(Order::Rows) => vec![&Rows],
+        Some(Order::RightToLeft) => vec![&Columns::RightToLeft],
+        Some(Order::LeftToRight) => vec![&Columns::LeftToRight],
+        None => vec![&Rows, &Columns::RightToLeft],
+    };
+    let totals = orders
+        .into_iter()
+        .map(|order| day06::total(&input, order))
+        .collect::<Result<Vec<_>, _>>();
+    match totals.as_deref() {
+        Ok([total]) => println!("Grand total: {}", total),
+        Ok([part1, part2]) => aoc_core::print_answers(part1, part2),
+        Ok(_) => unreachable!("one total per reading order"),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
         }
-        index -= 1;
     }
 
-    Input2 { problems: problems }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        let input = parse(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result1 = part1(&input);
-
-        assert_eq!(result1, 4277556);
-    }
-
-    #[test]
-    fn test_part2() {
-        let input = parse2(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt"));
-        let result2 = part2(&input);
-
-        assert_eq!(result2, 3263827);
-    }
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,286 @@
+use aoc_core::Checked;
+use std::fmt::{self, Display};
+
+/// The integer problems are worked out in, with every step checked for overflow. Build with the `bigint` feature for
+/// arbitrary precision.
+pub type Number = aoc_core::Signed;
+
+/// `a` and `b` combined by `operator`, or `None` if the answer doesn't fit. Division by zero and negative exponents
+/// are ruled out before this.
+fn arithmetic(operator: Operator, a: Number, b: Number) -> Option<Number> {
+    match operator {
+        Operator::Add => a.try_add(&b),
+        Operator::Subtract => a.try_sub(&b),
+        Operator::Multiply => a.try_mul(&b),
+        Operator::Divide => a.try_div(&b),
+        Operator::Remainder => a.try_rem(&b),
+        Operator::Power => match b.to_u32() {
+            Some(exponent) => a.try_pow(exponent),
+            // Past that only 0, 1 and -1 have powers that fit
+            None if a == Number::from(0) || a == Number::from(1) => Some(a),
+            None if a == Number::from(-1) && b.try_rem(&Number::from(2))? != Number::from(0) => {
+                Some(a)
+            }
+            None if a == Number::from(-1) => Some(Number::from(1)),
+            None => None,
+        },
+        Operator::Min => Some(a.min(b)),
+        Operator::Max => Some(a.max(b)),
+    }
+}
+
+/// The operators a worksheet problem can use, one per problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    /// Integer division, rounding towards zero
+    Divide,
+    /// The remainder has the sign of the number being divided
+    Remainder,
+    Power,
+    Min,
+    Max,
+}
+
+/// Which way a problem's numbers are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fold {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a ^ b ^ c` is `a ^ (b ^ c)`
+    Right,
+}
+
+/// Why a problem has no answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Failure {
+    NoNumbers,
+    DivisionByZero,
+    NegativeExponent,
+    /// The problem's answer, one of its numbers, or the grand total after adding its answer is too large
+    Overflow,
+}
+
+/// A problem that couldn't be worked out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EvalError {
+    /// Index of the problem in the order it was evaluated
+    pub problem: usize,
+    pub operator: Operator,
+    pub failure: Failure,
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failure = match self.failure {
+            Failure::NoNumbers => "has no answer, it has no numbers",
+            Failure::DivisionByZero => "has no answer, it divides by zero",
+            Failure::NegativeExponent => "has no answer, it raises to a negative power",
+            Failure::Overflow => aoc_core::OVERFLOWED,
+        };
+        write!(
+            f,
+            "error: problem {} ({}) {}",
+            self.problem + 1,
+            self.operator.glyph(),
+            failure
+        )
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+impl Operator {
+    pub const ALL: [Operator; 8] = [
+        Operator::Add,
+        Operator::Subtract,
+        Operator::Multiply,
+        Operator::Divide,
+        Operator::Remainder,
+        Operator::Power,
+        Operator::Min,
+        Operator::Max,
+    ];
+
+    /// What the operator looks like in a worksheet. Min and max are `<` and `>`.
+    pub const fn glyph(self) -> char {
+        match self {
+            Operator::Add => '+',
+            Operator::Subtract => '-',
+            Operator::Multiply => '*',
+            Operator::Divide => '/',
+            Operator::Remainder => '%',
+            Operator::Power => '^',
+            Operator::Min => '<',
+            Operator::Max => '>',
+        }
+    }
+
+    pub fn from_glyph(glyph: char) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|operator| operator.glyph() == glyph)
+    }
+
+    /// Powers fold right like they do in maths. It makes no difference for the operators that are associative, so
+    /// they fold left along with the rest.
+    pub const fn fold(self) -> Fold {
+        match self {
+            Operator::Power => Fold::Right,
+            _ => Fold::Left,
+        }
+    }
+
+    fn apply(self, a: Number, b: Number) -> Result<Number, Failure> {
+        match self {
+            Operator::Divide | Operator::Remainder if b == Number::from(0) => {
+                Err(Failure::DivisionByZero)
+            }
+            Operator::Power if b < Number::from(0) => Err(Failure::NegativeExponent),
+            _ => arithmetic(self, a, b).ok_or(Failure::Overflow),
+        }
+    }
+
+    /// Combines `numbers` with the operator, in the direction it folds.
+    pub fn evaluate(self, numbers: &[Number]) -> Result<Number, Failure> {
+        match self.fold() {
+            Fold::Left => {
+                let mut numbers = numbers.iter().cloned();
+                let first = numbers.next().ok_or(Failure::NoNumbers)?;
+                numbers.try_fold(first, |result, number| self.apply(result, number))
+            }
+            Fold::Right => {
+                let mut numbers = numbers.iter().rev().cloned();
+                let last = numbers.next().ok_or(Failure::NoNumbers)?;
+                numbers.try_fold(last, |result, number| self.apply(number, result))
+            }
+        }
+    }
+}
+
+/// Adds up the answers to `problems`, stopping at the first one without an answer or that takes the total out of
+/// range.
+pub fn grand_total<'a>(
+    problems: impl Iterator<Item = (&'a [Number], Operator)>,
+) -> Result<Number, EvalError> {
+    problems
+        .enumerate()
+        .try_fold(Number::from(0), |total, (problem, (numbers, operator))| {
+            let error = |failure| EvalError {
+                problem,
+                operator,
+                failure,
+            };
+            let answer = operator.evaluate(numbers).map_err(error)?;
+            arithmetic(Operator::Add, total, answer).ok_or(error(Failure::Overflow))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(numbers: &[i32]) -> Vec<Number> {
+        numbers.iter().map(|n| Number::from(*n)).collect()
+    }
+
+    fn evaluate(glyph: char, numbers: &[Number]) -> Result<Number, Failure> {
+        Operator::from_glyph(glyph).unwrap().evaluate(numbers)
+    }
+
+    /// The number written out in decimal
+    fn decimal(digits: &str) -> Number {
+        Number::from_digits(&digits.bytes().map(|digit| digit - b'0').collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let evaluate = |glyph, list: &[i32]| evaluate(glyph, &numbers(list));
+        let n = Number::from;
+
+        assert_eq!(evaluate('+', &[1, 2, 3]), Ok(n(6)));
+        assert_eq!(evaluate('-', &[10, 3, 2]), Ok(n(5)));
+        assert_eq!(evaluate('*', &[2, 3, 4]), Ok(n(24)));
+        assert_eq!(evaluate('/', &[100, 5, 3]), Ok(n(6)));
+        assert_eq!(evaluate('%', &[100, 7, 4]), Ok(n(2)));
+        assert_eq!(evaluate('^', &[2, 3, 2]), Ok(n(512)));
+        assert_eq!(evaluate('<', &[5, 2, 9]), Ok(n(2)));
+        assert_eq!(evaluate('>', &[5, 2, 9]), Ok(n(9)));
+        assert_eq!(evaluate('-', &[7]), Ok(n(7)));
+
+        assert_eq!(evaluate('/', &[1, 0]), Err(Failure::DivisionByZero));
+        assert_eq!(evaluate('%', &[1, 2, 0]), Err(Failure::DivisionByZero));
+        assert_eq!(evaluate('^', &[2, 3, 0, 2]), Ok(n(2)));
+        assert_eq!(evaluate('^', &[2, -1]), Err(Failure::NegativeExponent));
+        assert_eq!(evaluate('+', &[]), Err(Failure::NoNumbers));
+        assert_eq!(Operator::from_glyph('x'), None);
+    }
+
+    #[test]
+    fn test_grand_total_reports_problem() {
+        let problems = [
+            (numbers(&[1, 2]), Operator::Add),
+            (numbers(&[4, 0]), Operator::Multiply),
+            (numbers(&[4, 0]), Operator::Divide),
+        ];
+        let problems = || {
+            problems
+                .iter()
+                .map(|(numbers, operator)| (numbers.as_slice(), *operator))
+        };
+
+        assert_eq!(
+            grand_total(problems()),
+            Err(EvalError {
+                problem: 2,
+                operator: Operator::Divide,
+                failure: Failure::DivisionByZero
+            })
+        );
+        assert_eq!(grand_total(problems().take(2)), Ok(Number::from(3)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let big = || decimal(&format!("1{}", "0".repeat(20)));
+        let one = || Number::from(1);
+
+        // Only 0, 1 and -1 can be raised to a power that large
+        assert_eq!(evaluate('^', &[one(), big()]), Ok(one()));
+        assert_eq!(
+            evaluate('^', &[Number::from(-1), big() + one()]),
+            Ok(Number::from(-1))
+        );
+        assert_eq!(
+            evaluate('^', &[Number::from(2), big()]),
+            Err(Failure::Overflow)
+        );
+
+        let product = evaluate('*', &[big(), big(), big()]);
+        let total = grand_total(
+            [
+                (&[big(), big()][..], Operator::Multiply),
+                (&[big(), big()][..], Operator::Multiply),
+            ]
+            .into_iter(),
+        );
+        let digits = [9; 40];
+
+        #[cfg(not(feature = "bigint"))]
+        {
+            assert_eq!(product, Err(Failure::Overflow));
+            assert_eq!(Number::from_digits(&digits), None);
+            let error = total.unwrap_err();
+            assert_eq!(error.problem, 0);
+            assert!(error.to_string().contains("problem 1 (*) overflowed"));
+        }
+        #[cfg(feature = "bigint")]
+        {
+            assert_eq!(product, Ok(decimal(&format!("1{}", "0".repeat(60)))));
+            assert_eq!(Number::from_digits(&digits), Some(decimal(&"9".repeat(40))));
+            assert_eq!(total, Ok(decimal(&format!("2{}", "0".repeat(40)))));
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crate::Operator;
+use aoc_core::{Line, ParseError};
+use itertools::Itertools;
+use std::fmt::{self, Display};
+
+/// One problem's patch of the worksheet, from the column it starts in up to the next column that is blank all the
+/// way down.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Problem {
+    /// The first column, counting from 0
+    pub column: usize,
+    /// The digits in each row above the operator, with `None` for spaces. Every row is as wide as the problem.
+    pub cells: Vec<Vec<Option<u8>>>,
+    pub operator: Operator,
+    /// Which of the problem's columns the operator is in
+    pub operator_column: usize,
+}
+
+impl Problem {
+    pub fn width(&self) -> usize {
+        self.cells
+            .first()
+            .map_or(self.operator_column + 1, |row| row.len())
+    }
+}
+
+/// A worksheet laid out in columns, with its problems separated by blank columns and operators along the bottom.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Worksheet {
+    pub problems: Vec<Problem>,
+    /// Where the last problem ends, shorter lines being padded out with spaces. Blank columns after it, like
+    /// trailing spaces, are left out.
+    pub width: usize,
+}
+
+/// How to read the numbers out of a problem.
+pub trait ReadingOrder {
+    /// The numbers in `problem` in the order they are combined, each as its digits, most significant first.
+    fn numbers(&self, problem: &Problem) -> Vec<Vec<u8>>;
+}
+
+/// Each row is a number, read top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rows;
+
+/// Each column is a number with its most significant digit at the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Columns {
+    RightToLeft,
+    LeftToRight,
+}
+
+/// The digits among `cells`, or `None` if they are all spaces. Spaces between digits are skipped.
+fn number(cells: impl Iterator<Item = Option<u8>>) -> Option<Vec<u8>> {
+    let digits = cells.flatten().collect_vec();
+    (!digits.is_empty()).then_some(digits)
+}
+
+impl ReadingOrder for Rows {
+    fn numbers(&self, problem: &Problem) -> Vec<Vec<u8>> {
+        problem
+            .cells
+            .iter()
+            .filter_map(|row| number(row.iter().copied()))
+            .collect()
+    }
+}
+
+impl ReadingOrder for Columns {
+    fn numbers(&self, problem: &Problem) -> Vec<Vec<u8>> {
+        let column = |x: usize| number(problem.cells.iter().map(|row| row[x]));
+        // A column of spaces has no number, unlike one of zeros
+        match self {
+            Columns::RightToLeft => (0..problem.width()).rev().filter_map(column).collect(),
+            Columns::LeftToRight => (0..problem.width()).filter_map(column).collect(),
+        }
+    }
+}
+
+/// An error pointing at the character in `column` of `line`, or past its end if the line is shorter than that.
+fn error_at(line: &Line, column: usize, expected: &str) -> ParseError {
+    match line.text.char_indices().nth(column) {
+        Some((offset, c)) => line.error(&line.text[offset..offset + c.len_utf8()], expected),
+        None => line.error_at_end(expected),
+    }
+}
+
+impl Worksheet {
+    /// Rows shorter than the longest are treated as if they were padded with spaces.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = aoc_core::lines(input).collect_vec();
+        let Some((operator_line, number_lines)) = lines.split_last() else {
+            return Err(ParseError::end_of_input(input, "a worksheet"));
+        };
+
+        let width = lines
+            .iter()
+            .map(|line| line.text.chars().count())
+            .max()
+            .unwrap_or(0);
+        let grid = lines
+            .iter()
+            .map(|line| line.text.chars().pad_using(width, |_| ' ').collect_vec())
+            .collect_vec();
+        let (operator_row, number_rows) = grid.split_last().unwrap();
+
+        let is_blank = |x: usize| grid.iter().all(|row| row[x] == ' ');
+        let mut problems = vec![];
+        let mut x = 0;
+        while x < width {
+            if is_blank(x) {
+                x += 1;
+                continue;
+            }
+            let column = x;
+            while x < width && !is_blank(x) {
+                x += 1;
+            }
+
+            let mut glyphs = (column..x).filter(|x| operator_row[*x] != ' ');
+            let operator_x = glyphs
+                .next()
+                .ok_or_else(|| error_at(operator_line, column, "an operator under this problem"))?;
+            if let Some(extra) = glyphs.next() {
+                return Err(error_at(operator_line, extra, "one operator per problem"));
+            }
+            let operator = Operator::from_glyph(operator_row[operator_x]).ok_or_else(|| {
+                error_at(operator_line, operator_x, "an operator: + - * / % ^ < or >")
+            })?;
+
+            let cells = number_rows
+                .iter()
+                .zip(number_lines.iter())
+                .map(|(row, line)| {
+                    (column..x)
+                        .map(|x| match row[x] {
+                            ' ' => Ok(None),
+                            c => c
+                                .to_digit(10)
+                                .map(|digit| Some(digit as u8))
+                                .ok_or_else(|| error_at(line, x, "a digit")),
+                        })
+                        .collect()
+                })
+                .collect::<Result<_, _>>()?;
+
+            problems.push(Problem {
+                column,
+                cells,
+                operator,
+                operator_column: operator_x - column,
+            });
+        }
+
+        let width = problems
+            .last()
+            .map_or(0, |problem| problem.column + problem.width());
+        Ok(Self { problems, width })
+    }
+
+    /// Every problem's numbers as read by `order`, along with its operator, from left to right.
+    pub fn read<'a>(
+        &'a self,
+        order: &'a dyn ReadingOrder,
+    ) -> impl Iterator<Item = (Vec<Vec<u8>>, Operator)> + 'a {
+        self.problems
+            .iter()
+            .map(move |problem| (order.numbers(problem), problem.operator))
+    }
+}
+
+/// The worksheet laid out in columns again, with every line padded to the full width.
+impl Display for Worksheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self
+            .problems
+            .first()
+            .map_or(0, |problem| problem.cells.len());
+        for y in 0..=rows {
+            let mut line = vec![' '; self.width];
+            for problem in self.problems.iter() {
+                if y == rows {
+                    line[problem.column + problem.operator_column] = problem.operator.glyph();
+                    continue;
+                }
+                for (x, cell) in problem.cells[y].iter().enumerate() {
+                    if let Some(digit) = cell {
+                        line[problem.column + x] = (b'0' + digit) as char;
+                    }
+                }
+            }
+            writeln!(f, "{}", line.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(worksheet: &Worksheet, order: &dyn ReadingOrder) -> Vec<Vec<String>> {
+        worksheet
+            .read(order)
+            .map(|(numbers, _)| {
+                numbers
+                    .iter()
+                    .map(|digits| digits.iter().join(""))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ragged_rows_and_zero_columns() {
+        // The second and last lines stop short, and the middle column of the first problem is all zeros
+        let worksheet = Worksheet::parse("101  7\n2\n303 42\n*   +").unwrap();
+
+        assert_eq!(worksheet.width, 6);
+        assert_eq!(worksheet.problems.len(), 2);
+        assert_eq!(worksheet.problems[1].column, 4);
+        assert_eq!(worksheet.problems[1].operator, Operator::Add);
+        assert_eq!(
+            read(&worksheet, &Rows),
+            vec![vec!["101", "2", "303"], vec!["7", "42"]]
+        );
+        assert_eq!(
+            read(&worksheet, &Columns::RightToLeft),
+            vec![vec!["13", "00", "123"], vec!["72", "4"]]
+        );
+        assert_eq!(
+            read(&worksheet, &Columns::LeftToRight),
+            vec![vec!["123", "00", "13"], vec!["4", "72"]]
+        );
+    }
+
+    #[test]
+    fn test_layout_errors() {
+        let error = |input| Worksheet::parse(input).unwrap_err().to_string();
+
+        assert!(error("12 3\n   +").contains("expected an operator under this problem"));
+        assert!(error("123\n+ *").contains("expected one operator per problem"));
+        assert!(error("1a 3\n+  *").contains("expected a digit"));
+        assert!(error("12\n#").contains("expected an operator: "));
+    }
+}
@@ -0,0 +1,283 @@
+use aoc_core::{Checked, Grid, ParseError, Point, Solution};
+use itertools::Itertools;
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
+
+// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// struct Position {
+//     x: i64,
+//     y: i64,
+// }
+
+#[derive(Debug, Clone, Hash)]
+pub struct Input {
+    values: Grid<char>,
+    // Where the beam enters, at the S on the first line
+    start: Point,
+}
+
+/// The integer timelines are counted in. A u128 is enough for a manifold about 127 splitters deep, past that build
+/// with the `bigint` feature.
+pub type Timelines = aoc_core::Unsigned;
+
+/// Adds `count` to `total`, or returns `None` if it doesn't fit.
+fn add(total: &mut Timelines, count: &Timelines) -> Option<()> {
+    *total = total.try_add(count)?;
+    Some(())
+}
+
+/// There were too many timelines to count once the beams had passed through this row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimelineOverflow {
+    /// 1-based, like the line numbers of the input
+    pub row: usize,
+}
+
+impl Display for TimelineOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error: the timelines at row {} {}",
+            self.row,
+            aoc_core::OVERFLOWED
+        )
+    }
+}
+
+impl std::error::Error for TimelineOverflow {}
+
+/// How many timelines a particle entering at the start ends up in, or the row where there got to be too many. Works
+/// down a row at a time, keeping the number of timelines in each column of the row. A splitter sends them diagonally
+/// down to both sides, and a side that is past the edge of the manifold ends those timelines there, the same as
+/// leaving through the bottom does.
+pub fn timelines(input: &Input) -> Result<Timelines, TimelineOverflow> {
+    let width = input.values.width();
+    let mut counts = vec![Timelines::from(0_u8); width];
+    counts[input.start.x] = Timelines::from(1_u8);
+    let mut finished = Timelines::from(0_u8);
+
+    for (y, row) in input.values.rows().enumerate() {
+        let overflow = TimelineOverflow { row: y + 1 };
+        let mut next = vec![Timelines::from(0_u8); width];
+        for (x, count) in counts.iter().enumerate() {
+            if row[x] != '^' {
+                add(&mut next[x], count).ok_or(overflow)?;
+                continue;
+            }
+            for side in [x.checked_sub(1), Some(x + 1).filter(|x| *x < width)] {
+                match side {
+                    Some(x) => add(&mut next[x], count).ok_or(overflow)?,
+                    None => add(&mut finished, count).ok_or(overflow)?,
+                }
+            }
+        }
+        counts = next;
+    }
+
+    // Adding up the ones that left through the bottom is charged to the last row
+    let overflow = TimelineOverflow {
+        row: input.values.height(),
+    };
+    for count in counts.iter() {
+        add(&mut finished, count).ok_or(overflow)?;
+    }
+    Ok(finished)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Input;
+    type Output1 = i64;
+    type Output2 = Result<Timelines, TimelineOverflow>;
+
+    fn part1(input: &Input) -> i64 {
+        let width = input.values.width();
+        let height = input.values.height();
+        let mut beams_to_process: Vec<Point> = Vec::new();
+        beams_to_process.push(input.start);
+        let mut processed_splitters = HashSet::new();
+
+        let mut splits = 0;
+        while let Some(beam) = beams_to_process.pop() {
+            if beam.y < height - 1 {
+                let cell = input.values[beam];
+                if cell == '^' {
+                    if processed_splitters.contains(&beam) {
+                        continue;
+                    }
+                    if beam.x > 0 {
+                        beams_to_process.push(Point::new(beam.x - 1, beam.y));
+                    }
+                    if beam.x < width - 1 {
+                        beams_to_process.push(Point::new(beam.x + 1, beam.y));
+                    }
+                    splits += 1;
+                    processed_splitters.insert(beam);
+                } else {
+                    beams_to_process.push(Point::new(beam.x, beam.y + 1));
+                }
+            }
+        }
+
+        splits
+    }
+
+    fn part2(input: &Input) -> Result<Timelines, TimelineOverflow> {
+        timelines(input)
+    }
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        let lines = aoc_core::lines(input).collect_vec();
+        let first = lines
+            .first()
+            .ok_or_else(|| ParseError::end_of_input(input, "the manifold"))?;
+        // S is the start of the beam on the first line
+        let start = first
+            .text
+            .chars()
+            .position(|c| c == 'S')
+            .ok_or_else(|| first.error(first.text, "a line containing the start 'S'"))?;
+
+        let values = Grid::parse(input, Some, "a row of the manifold")?;
+
+        Ok(Input {
+            values,
+            start: Point::new(start, 0),
+        })
+
+        /*
+         * Alternative implementations:
+         */
+
+        // Two sections separated by a newline
+        // Input {
+        //     first: lines
+        //         .iter()
+        //         .take_while(|line| !line.is_empty())
+        //         .map(|line| line.split_once('|').unwrap())
+        //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
+        //         .collect_vec(),
+        //     second: lines
+        //         .iter()
+        //         .skip_while(|line| !line.is_empty())
+        //         .filter(|line| !line.is_empty())
+        //         .map(|line| {
+        //             line.split(',')
+        //                 .map(|page| page.parse::<i64>().unwrap())
+        //                 .collect_vec()
+        //         })
+        //         .collect_vec(),
+        // }
+
+        // Creates a HashMap<char, Vec<Position>>
+        // let map_limits = Position {
+        //     x: lines[0].len() as i64,
+        //     y: lines.len() as i64,
+        // };
+
+        // Input {
+        //     antennas: lines
+        //         .into_iter()
+        //         .enumerate()
+        //         .flat_map(|(y, line)| {
+        //             line.chars()
+        //                 .enumerate()
+        //                 .filter(|(_, c)| *c != '.')
+        //                 .map(|(x, c)| {
+        //                     (
+        //                         c,
+        //                         Position {
+        //                             x: x as i64,
+        //                             y: y as i64,
+        //                         },
+        //                     )
+        //                 })
+        //                 .collect_vec()
+        //         })
+        //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
+        //         .chunk_by(|(c, _)| *c)
+        //         .into_iter()
+        //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
+        //         .collect(),
+        //     map_limits,
+        // }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+            Day07::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result1 = Day07::part1(&input);
+
+        assert_eq!(result1, 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+            Day07::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result2 = Day07::part2(&input);
+
+        assert_eq!(result2, Ok(Timelines::from(40_u8)));
+    }
+
+    #[test]
+    fn test_splitters_on_the_edges() {
+        // The beam split off past the side ends its timeline, the other carries on to the bottom
+        let left = Day07::parse("S..\n^..\n...").unwrap();
+        let right = Day07::parse("..S\n..^\n...").unwrap();
+
+        assert_eq!(timelines(&left), Ok(Timelines::from(2_u8)));
+        assert_eq!(timelines(&right), Ok(Timelines::from(2_u8)));
+    }
+
+    #[test]
+    fn test_deep_manifolds() {
+        // Every pair of rows doubles the timelines in the middle column, giving 3 * 2^pairs - 2 in all
+        let manifold = |pairs| format!(".S.\n{}", ".^.\n^.^\n".repeat(pairs));
+        let expected = |pairs| {
+            Timelines::from(3_u8) * (Timelines::from(1_u8) << pairs) - Timelines::from(2_u8)
+        };
+
+        let input = Day07::parse(&manifold(120)).unwrap();
+        assert_eq!(timelines(&input), Ok(expected(120)));
+
+        let input = Day07::parse(&manifold(130)).unwrap();
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(timelines(&input), Err(TimelineOverflow { row: 257 }));
+        #[cfg(feature = "bigint")]
+        assert_eq!(timelines(&input), Ok(expected(130)));
+
+        // Far deeper than the stack would have allowed recursing once per row
+        let input = Day07::parse(&format!("S\n{}", ".\n".repeat(1_000_000))).unwrap();
+        assert_eq!(timelines(&input), Ok(Timelines::from(1_u8)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Day07::parse(input).unwrap_err();
+
+        let e = error("...\n.^.\n");
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (1, 1, "a line containing the start 'S'")
+        );
+        let e = error(".S.\n.^. \n");
+        assert_eq!(
+            (e.line, e.column, e.found.as_str(), e.expected.as_str()),
+            (2, 1, ".^. ", "a row 3 wide")
+        );
+        assert_eq!(
+            format!("{:?}", Day07::parse(".S.\r\n.^.\r\n").unwrap()),
+            format!("{:?}", Day07::parse(".S.\n.^.\n").unwrap())
+        );
+    }
+}
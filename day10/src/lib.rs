@@ -0,0 +1,419 @@
+use aoc_core::{Line, ParseError, Solution};
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::{
+    collections::{BinaryHeap, HashSet, VecDeque},
+    fmt::format,
+};
+use z3::ast::Int;
+use z3::{Optimize, Solver};
+
+// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// struct Position {
+//     x: i64,
+//     y: i64,
+// }
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Button {
+    light_indexes: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Machine {
+    lights_count: i64,
+    target: Vec<bool>,
+    buttons: Vec<Button>,
+    joltage_target: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Hash)]
+pub struct Input {
+    values: Vec<Machine>,
+}
+
+fn get_machine_presses_part1(machine: &Machine) -> i64 {
+    let mut seen_states = HashSet::new();
+    let mut states_to_process = VecDeque::new();
+    let mut starting_state = Vec::new();
+    starting_state.resize(machine.lights_count as usize, false);
+    states_to_process.push_back((0, starting_state));
+
+    loop {
+        let (moves, start) = states_to_process.pop_front().unwrap();
+        if seen_states.contains(&start) {
+            continue;
+        }
+        seen_states.insert(start.clone());
+
+        if machine.target == start {
+            return moves;
+        }
+
+        for button in machine.buttons.iter() {
+            let mut modified = start.clone();
+            for light in button.light_indexes.iter() {
+                modified[*light] = !modified[*light];
+            }
+            states_to_process.push_back(((moves + 1), modified));
+        }
+    }
+}
+
+fn get_machine_presses_part2(machine: &Machine) -> i64 {
+    // This is not an optimal solution, but it should run in less than half an hour
+    let mut seen_states = HashSet::new();
+    let mut states_to_process = VecDeque::new();
+    let mut starting_state = Vec::new();
+    starting_state.resize(machine.joltage_target.len(), 0);
+    states_to_process.push_back((0, starting_state));
+    let progress = indicatif::ProgressBar::new_spinner();
+    let target_indexes_in_increasing_order = machine
+        .joltage_target
+        .iter()
+        .enumerate()
+        .sorted_by_key(|(_index, target)| **target)
+        .map(|(index, _)| index)
+        .collect_vec();
+
+    loop {
+        let (moves, start) = states_to_process.pop_front().unwrap();
+        if seen_states.contains(&start) {
+            continue;
+        }
+        seen_states.insert(start.clone());
+        progress.set_message(format!(
+            "{:?}: {:?}   {} left",
+            machine.joltage_target,
+            start,
+            states_to_process.len()
+        ));
+
+        if machine.joltage_target == start {
+            return moves;
+        }
+
+        // See if we can multiply this to get to the end
+        if start[0] > 0 && machine.joltage_target[0] % start[0] == 0 {
+            let divisor = machine.joltage_target[0] / start[0];
+            let mut is_multipliable = true;
+            for i in 1..machine.joltage_target.len() {
+                if start[i] == 0
+                    || machine.joltage_target[i] % start[i] != 0
+                    || machine.joltage_target[i] / start[i] != divisor
+                {
+                    is_multipliable = false;
+                    break;
+                }
+            }
+
+            if is_multipliable {
+                let total_moves = moves * divisor;
+                return total_moves;
+            }
+        }
+
+        for i in target_indexes_in_increasing_order.iter().cloned() {
+            if start[i] != machine.joltage_target[i] {
+                for button in machine.buttons.iter() {
+                    if !button.light_indexes.contains(&i) {
+                        continue;
+                    }
+                    let mut modified = start.clone();
+                    let mut is_valid = true;
+                    for light in button.light_indexes.iter() {
+                        modified[*light] += 1;
+                        if modified[*light] > machine.joltage_target[*light] {
+                            is_valid = false;
+                            break;
+                        }
+                    }
+                    if is_valid {
+                        states_to_process.push_back(((moves + 1), modified));
+                    }
+                }
+                break;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+struct State {
+    best_case_total_cost: i64,
+    cost: i64,
+    indicators: Vec<i64>,
+}
+
+// The priority queue depends on `Ord`.
+// Explicitly implement the trait so the queue becomes a min-heap
+// instead of a max-heap.
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Notice that we flip the ordering on costs.
+        // In case of a tie we compare positions - this step is necessary
+        // to make implementations of `PartialEq` and `Ord` consistent.
+        other
+            .best_case_total_cost
+            .cmp(&self.best_case_total_cost)
+            .then_with(|| self.cost.cmp(&other.cost))
+            .then_with(|| self.indicators.cmp(&other.indicators))
+    }
+}
+
+// `PartialOrd` needs to be implemented as well.
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn get_machine_presses_part2_optimized(machine: &Machine) -> i64 {
+    // Let's use z3
+    let solver = Optimize::new();
+
+    // One int for each button
+    let button_ints = machine
+        .buttons
+        .iter()
+        .enumerate()
+        .map(|(i, button)| (button, Int::new_const("btn".to_string() + &i.to_string())))
+        .collect_vec();
+
+    let mut buttons_sum: Int = button_ints[0].1.clone();
+    for i in 1..button_ints.len() {
+        buttons_sum = buttons_sum + &button_ints[i].1;
+    }
+    solver.minimize(&buttons_sum);
+    for button in button_ints.iter() {
+        solver.assert(&button.1.ge(0));
+    }
+
+    for indicator in 0..machine.joltage_target.len() {
+        let buttons_involved = button_ints
+            .iter()
+            .filter(|(b, _i)| b.light_indexes.contains(&indicator))
+            .map(|b| &b.1)
+            .collect_vec();
+        if buttons_involved.is_empty() {
+            println!("Unexpected!");
+            continue;
+        }
+
+        let mut total: Int = buttons_involved[0].clone();
+        for i in 1..buttons_involved.len() {
+            total = total + buttons_involved[i];
+        }
+        solver.assert(&total.eq(machine.joltage_target[indicator]));
+    }
+    solver.check(&[]);
+    let model = solver.get_model().unwrap();
+
+    model.eval(&buttons_sum, true).unwrap().as_i64().unwrap()
+}
+
+/// The contents of `part` between its `open` and `close` characters.
+fn bracketed<'a>(
+    line: &Line,
+    part: &'a str,
+    open: char,
+    close: char,
+) -> Result<&'a str, ParseError> {
+    part.strip_prefix(open)
+        .and_then(|p| p.strip_suffix(close))
+        .ok_or_else(|| line.error(part, &format!("a list wrapped in {}{}", open, close)))
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Input;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn part1(input: &Input) -> i64 {
+        let mut sum = 0;
+
+        for machine in input.values.iter() {
+            sum += get_machine_presses_part1(machine);
+        }
+
+        sum
+    }
+
+    fn part2(input: &Input) -> i64 {
+        input
+            .values
+            .iter()
+            .map(|machine| get_machine_presses_part2_optimized(machine))
+            .sum::<i64>()
+    }
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        let lines = aoc_core::lines(input).collect_vec();
+
+        Ok(Input {
+            values: lines
+                .iter()
+                .map(|line| {
+                    // Line looks like [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+                    let parts = line.text.split_ascii_whitespace().collect_vec();
+                    if parts.len() < 2 {
+                        return Err(line.error(line.text, "a light diagram, buttons and joltages"));
+                    }
+                    let target =
+                        bracketed(line, parts[0], '[', ']')?
+                            .char_indices()
+                            .map(|(i, c)| match c {
+                                '.' => Ok(false),
+                                '#' => Ok(true),
+                                _ => Err(line
+                                    .error(&parts[0][i + 1..i + 1 + c.len_utf8()], "'#' or '.'")),
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+
+                    let mut buttons = Vec::new();
+                    for part in parts[1..(parts.len() - 1)].iter() {
+                        buttons.push(Button {
+                            light_indexes: bracketed(line, part, '(', ')')?
+                                .split(',')
+                                .map(|light| {
+                                    let index = line.parse::<usize>(light, "a light index")?;
+                                    if index >= target.len() {
+                                        return Err(line.error(
+                                            light,
+                                            &format!("a light index below {}", target.len()),
+                                        ));
+                                    }
+                                    Ok(index)
+                                })
+                                .collect::<Result<_, _>>()?,
+                        });
+                    }
+                    let joltage_part = parts[parts.len() - 1];
+                    let joltage_target = bracketed(line, joltage_part, '{', '}')?
+                        .split(',')
+                        .map(|j| line.parse::<i64>(j, "a joltage"))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if joltage_target.len() != target.len() {
+                        return Err(line.error(
+                            joltage_part,
+                            &format!("{} joltages, one per light", target.len()),
+                        ));
+                    }
+
+                    Ok(Machine {
+                        lights_count: target.len() as i64,
+                        target,
+                        buttons,
+                        joltage_target,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+
+        /*
+         * Alternative implementations:
+         */
+
+        // Two sections separated by a newline
+        // Input {
+        //     first: lines
+        //         .iter()
+        //         .take_while(|line| !line.is_empty())
+        //         .map(|line| line.split_once('|').unwrap())
+        //         .map(|(a, b)| (a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()))
+        //         .collect_vec(),
+        //     second: lines
+        //         .iter()
+        //         .skip_while(|line| !line.is_empty())
+        //         .filter(|line| !line.is_empty())
+        //         .map(|line| {
+        //             line.split(',')
+        //                 .map(|page| page.parse::<i64>().unwrap())
+        //                 .collect_vec()
+        //         })
+        //         .collect_vec(),
+        // }
+
+        // Creates a HashMap<char, Vec<Position>>
+        // let map_limits = Position {
+        //     x: lines[0].len() as i64,
+        //     y: lines.len() as i64,
+        // };
+
+        // Input {
+        //     antennas: lines
+        //         .into_iter()
+        //         .enumerate()
+        //         .flat_map(|(y, line)| {
+        //             line.chars()
+        //                 .enumerate()
+        //                 .filter(|(_, c)| *c != '.')
+        //                 .map(|(x, c)| {
+        //                     (
+        //                         c,
+        //                         Position {
+        //                             x: x as i64,
+        //                             y: y as i64,
+        //                         },
+        //                     )
+        //                 })
+        //                 .collect_vec()
+        //         })
+        //         .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
+        //         .chunk_by(|(c, _)| *c)
+        //         .into_iter()
+        //         .map(|(c, positions)| (c, positions.map(|(_, p)| p).collect_vec()))
+        //         .collect(),
+        //     map_limits,
+        // }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+            Day10::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result1 = Day10::part1(&input);
+
+        assert_eq!(result1, 7);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+            Day10::parse_file(&(env!("CARGO_MANIFEST_DIR").to_owned() + "/src/test1.txt")).unwrap();
+        let result2 = Day10::part2(&input);
+
+        assert_eq!(result2, 33);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Day10::parse(input).unwrap_err();
+
+        let e = error("[.##.] (3) (1,4) {3,5,4,7}\n");
+        assert_eq!(
+            (e.line, e.column, e.found.as_str(), e.expected.as_str()),
+            (1, 15, "4", "a light index below 4")
+        );
+        let e = error("[.##.] (3) {3,5,4,7}\n[.##.] (3) {3,5,4}\n");
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 12, "4 joltages, one per light")
+        );
+        // Parts are separated by whitespace, so a trailing space or CRLF changes nothing
+        let machine = "[.##.] (3) (1,3) {3,5,4,7}";
+        assert_eq!(
+            format!("{:?}", Day10::parse(&format!("{} \r\n", machine)).unwrap()),
+            format!("{:?}", Day10::parse(&format!("{}\n", machine)).unwrap())
+        );
+    }
+}
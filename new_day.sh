@@ -5,6 +5,10 @@ DAY="day$DAY_OF_MONTH"
 cp -R template $DAY
 cd $DAY
 sed -i "s/\"daytodo\"/\"$DAY\"/g" Cargo.toml
+sed -i "s/daytodo/$DAY/g; s/DayTodo/Day$DAY_OF_MONTH/g" src/*.rs
+# Register the day with the advent runner
+echo "$DAY = { path = \"../$DAY\" }" >> ../advent/Cargo.toml
+sed -i "/new_day.sh adds days above this line/i \\    Day::new($((10#$DAY_OF_MONTH)), \&$DAY::Day$DAY_OF_MONTH)," ../advent/src/days.rs
 cargo test
 
 rm src/data.txt